@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use hyper::Client;
+use hyper::header::{Header, HeaderFormat, Headers, UserAgent};
+use hyper::net::{NetworkConnector, NetworkStream};
+
+use super::{Edmunds, BASE_URL, MEDIA_BASE_URL};
+use observer::{LogObserver, RequestObserver};
+use rate_limit::RateLimiter;
+use retry::RetryPolicy;
+
+/// Configures an `Edmunds` client.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use edmunds::EdmundsBuilder;
+///
+/// let api = EdmundsBuilder::new("my api key")
+///               .base_url("http://localhost:8080")
+///               .read_timeout(Duration::from_secs(10))
+///               .user_agent("inventory-sync/1.0")
+///               .build();
+/// ```
+pub struct EdmundsBuilder {
+    api_key: String,
+    base_url: String,
+    media_base_url: String,
+    client: Option<Client>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    headers: Headers,
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    observer: Box<RequestObserver>,
+}
+
+impl EdmundsBuilder {
+    pub fn new<S: Into<String>>(api_key: S) -> Self {
+        EdmundsBuilder {
+            api_key: api_key.into(),
+            base_url: BASE_URL.to_string(),
+            media_base_url: MEDIA_BASE_URL.to_string(),
+            client: None,
+            read_timeout: None,
+            write_timeout: None,
+            headers: Headers::new(),
+            rate_limiter: RateLimiter::unlimited(),
+            retry_policy: RetryPolicy::never(),
+            observer: Box::new(LogObserver),
+        }
+    }
+
+    /// Scheme and host requests are sent to, `https://api.edmunds.com` by default.
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        let base_url = base_url.into();
+        self.base_url = base_url.trim_right_matches('/').to_string();
+        self
+    }
+
+    /// Host photo links are resolved against, `https://media.ed.edmunds-media.com` by default.
+    pub fn media_base_url<S: Into<String>>(mut self, media_base_url: S) -> Self {
+        let media_base_url = media_base_url.into();
+        self.media_base_url = media_base_url.trim_right_matches('/').to_string();
+        self
+    }
+
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets both the read and write timeouts.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.read_timeout(timeout).write_timeout(timeout)
+    }
+
+    pub fn user_agent<S: Into<String>>(self, user_agent: S) -> Self {
+        self.header(UserAgent(user_agent.into()))
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header<H: Header + HeaderFormat>(mut self, header: H) -> Self {
+        self.headers.set(header);
+        self
+    }
+
+    /// Adds a header without a typed hyper representation, e.g. `X-Request-Source`.
+    pub fn raw_header<K: Into<String>>(mut self, name: K, value: &str) -> Self {
+        self.headers.set_raw(name.into(), vec![value.as_bytes().to_vec()]);
+        self
+    }
+
+    /// Uses an already configured hyper `Client`.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Uses a hyper `Client` built on `connector`, e.g. a proxy or a mock.
+    pub fn connector<C, S>(self, connector: C) -> Self
+        where C: NetworkConnector<Stream = S> + Send + Sync + 'static,
+              S: NetworkStream + Send
+    {
+        self.client(Client::with_connector(connector))
+    }
+
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Replaces the default `LogObserver`.
+    pub fn observer<O: RequestObserver + 'static>(mut self, observer: O) -> Self {
+        self.observer = Box::new(observer);
+        self
+    }
+
+    pub fn build(self) -> Edmunds {
+        let mut client = self.client.unwrap_or_else(Client::new);
+
+        if self.read_timeout.is_some() {
+            client.set_read_timeout(self.read_timeout);
+        }
+        if self.write_timeout.is_some() {
+            client.set_write_timeout(self.write_timeout);
+        }
+
+        Edmunds {
+            api_key: self.api_key,
+            base_url: self.base_url,
+            media_base_url: self.media_base_url,
+            client: client,
+            headers: self.headers,
+            rate_limiter: self.rate_limiter,
+            retry_policy: self.retry_policy,
+            observer: self.observer,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// Score for one aspect of a vehicle, e.g. `Performance` or `Comfort`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryRating {
+    pub title: String,
+    pub value: f64,
+}
+
+/// Edmunds' own review of a model year.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditorialReview {
+    #[serde(rename="edmundsSays")]
+    pub edmunds_says: Option<String>,
+    #[serde(rename="whatsNew")]
+    pub whats_new: Option<String>,
+    #[serde(default)]
+    pub pros: Vec<String>,
+    #[serde(default)]
+    pub cons: Vec<String>,
+    /// Overall score out of 10.
+    pub rating: Option<f64>,
+    #[serde(default)]
+    pub ratings: Vec<CategoryRating>,
+}
+
+/// One page of owner reviews with the ratings aggregated over all of them.
+///
+/// Page through with `Query::page_num` and `Query::page_size`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConsumerReviews {
+    /// Overall score out of 5, across every review rather than just this page.
+    #[serde(rename="averageRating")]
+    pub average_rating: f64,
+    #[serde(rename="reviewsCount")]
+    pub reviews_count: u32,
+    #[serde(default)]
+    pub ratings: Vec<CategoryRating>,
+    #[serde(default)]
+    pub reviews: Vec<ConsumerReview>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConsumerReview {
+    pub id: String,
+    #[serde(rename="authorName")]
+    pub author: Option<String>,
+    pub title: String,
+    pub text: String,
+    #[serde(rename="favoriteFeatures")]
+    pub favorite_features: Option<String>,
+    #[serde(rename="suggestedImprovements")]
+    pub suggested_improvements: Option<String>,
+    /// Overall score out of 5.
+    #[serde(rename="averageRating")]
+    pub rating: f64,
+    #[serde(default)]
+    pub ratings: Vec<CategoryRating>,
+    pub created: String,
+}
@@ -0,0 +1,286 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+use hyper;
+use hyper::status::StatusCode;
+use serde_json;
+
+use rate_limit::Budget;
+use vin::VinError;
+
+/// How much of an offending response body is kept on a deserialization error.
+const SNIPPET_LEN: usize = 200;
+
+/// A `Result` whose error is an `edmunds::Error`.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// The error body Edmunds returns alongside a failed request.
+///
+/// Errors raised by the Mashery gateway in front of the API are not JSON; for
+/// those `status` holds the `X-Mashery-Error-Code` header and `message` the
+/// text of the body.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiError {
+    pub status: String,
+    pub message: String,
+    #[serde(rename="errorType")]
+    pub error_type: Option<String>,
+    #[serde(rename="moreInfoUrl")]
+    pub more_info_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent, or hyper failed to read the response.
+    Http(hyper::Error),
+    /// Reading the response body failed.
+    Io(io::Error),
+    /// The requested make, model, style, etc. does not exist.
+    NotFound(ApiError),
+    /// The API key is missing, unknown or inactive.
+    Unauthorized(StatusCode, ApiError),
+    /// The key went over its queries per second limit.
+    OverQps(ApiError),
+    /// The key used up its daily call budget.
+    OverQuota(ApiError),
+    /// Edmunds or Mashery failed with a 5xx status.
+    Server(StatusCode, ApiError),
+    /// Any other non-2xx response.
+    Status(StatusCode, ApiError),
+    /// The client-side rate limiter refused the request before it was sent.
+    RateLimited(Budget),
+    /// A VIN rejected by `Vin::parse`. The client never returns this itself;
+    /// it lets callers `try!(Vin::parse(..))` in functions returning `Result`.
+    InvalidVin(VinError),
+    /// A successful response whose body did not match the expected type.
+    Json {
+        endpoint: String,
+        snippet: String,
+        error: serde_json::Error,
+    },
+}
+
+impl Error {
+    /// The HTTP status of the response that caused this error, if there was one.
+    pub fn status(&self) -> Option<StatusCode> {
+        match *self {
+            Error::NotFound(_) => Some(StatusCode::NotFound),
+            Error::OverQps(_) | Error::OverQuota(_) => Some(StatusCode::Forbidden),
+            Error::Unauthorized(status, _) |
+            Error::Server(status, _) |
+            Error::Status(status, _) => Some(status),
+            _ => None,
+        }
+    }
+
+    /// The error body of the response that caused this error, if there was one.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match *self {
+            Error::NotFound(ref body) |
+            Error::OverQps(ref body) |
+            Error::OverQuota(ref body) |
+            Error::Unauthorized(_, ref body) |
+            Error::Server(_, ref body) |
+            Error::Status(_, ref body) => Some(body),
+            _ => None,
+        }
+    }
+}
+
+/// Builds a `Error::Json` keeping the start of the offending body.
+pub fn json(endpoint: &str, body: &str, error: serde_json::Error) -> Error {
+    Error::Json {
+        endpoint: endpoint.to_string(),
+        snippet: body.chars().take(SNIPPET_LEN).collect(),
+        error: error,
+    }
+}
+
+/// Classifies a non-2xx response.
+///
+/// `mashery_code` is the `X-Mashery-Error-Code` header, which is the only
+/// reliable way to tell the gateway's 403s apart.
+pub fn from_response(status: StatusCode, mashery_code: Option<&str>, body: &str) -> Error {
+    let api_error = match serde_json::from_str::<ApiError>(body) {
+        Ok(api_error) => api_error,
+        Err(_) => {
+            ApiError {
+                status: mashery_code.map(|c| c.to_string())
+                                    .unwrap_or_else(|| status.to_string()),
+                message: strip_tags(body),
+                error_type: None,
+                more_info_url: None,
+            }
+        }
+    };
+
+    let code = mashery_code.unwrap_or("");
+    let message = api_error.message.to_lowercase();
+
+    if code == "ERR_403_DEVELOPER_OVER_QPS" || message.contains("queries per second") ||
+       message.contains("over qps") {
+        return Error::OverQps(api_error);
+    }
+
+    if code == "ERR_403_DEVELOPER_OVER_RATE" || message.contains("over rate") ||
+       message.contains("queries per day") {
+        return Error::OverQuota(api_error);
+    }
+
+    match status {
+        StatusCode::NotFound => Error::NotFound(api_error),
+        StatusCode::Unauthorized => Error::Unauthorized(status, api_error),
+        StatusCode::Forbidden if code.starts_with("ERR_403_") || code.starts_with("ERR_401_") ||
+                                 message.contains("not authorized") ||
+                                 message.contains("inactive") => {
+            Error::Unauthorized(status, api_error)
+        }
+        _ if status.is_server_error() => Error::Server(status, api_error),
+        _ => Error::Status(status, api_error),
+    }
+}
+
+/// Reduces an HTML error page such as `<h1>Developer Over Qps</h1>` to its text.
+fn strip_tags(body: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.trim().to_string()
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Http(ref e) => write!(f, "http error: {}", e),
+            Error::Io(ref e) => write!(f, "io error: {}", e),
+            Error::NotFound(ref body) => write!(f, "not found: {}", body.message),
+            Error::Unauthorized(_, ref body) => write!(f, "unauthorized: {}", body.message),
+            Error::OverQps(ref body) => write!(f, "over qps limit: {}", body.message),
+            Error::OverQuota(ref body) => write!(f, "over daily quota: {}", body.message),
+            Error::Server(status, ref body) => {
+                write!(f, "server error ({}): {}", status, body.message)
+            }
+            Error::Status(status, ref body) => {
+                write!(f, "unexpected status ({}): {}", status, body.message)
+            }
+            Error::RateLimited(budget) => write!(f, "rate limited: {:?} budget exhausted", budget),
+            Error::InvalidVin(ref e) => write!(f, "invalid vin: {}", e),
+            Error::Json { ref endpoint, ref snippet, ref error } => {
+                write!(f, "could not deserialize response from {}: {} in {:?}",
+                       endpoint, error, snippet)
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Http(ref e) => e.description(),
+            Error::Io(ref e) => e.description(),
+            Error::NotFound(_) => "not found",
+            Error::Unauthorized(..) => "unauthorized",
+            Error::OverQps(_) => "over queries per second limit",
+            Error::OverQuota(_) => "over daily quota",
+            Error::Server(..) => "server error",
+            Error::Status(..) => "unexpected http status",
+            Error::RateLimited(_) => "client-side rate limit exhausted",
+            Error::InvalidVin(ref e) => e.description(),
+            Error::Json { .. } => "deserialization failed",
+        }
+    }
+
+    fn cause(&self) -> Option<&StdError> {
+        match *self {
+            Error::Http(ref e) => Some(e),
+            Error::Io(ref e) => Some(e),
+            Error::InvalidVin(ref e) => Some(e),
+            Error::Json { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<hyper::Error> for Error {
+    fn from(e: hyper::Error) -> Error {
+        Error::Http(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+/// So `try!(Vin::parse(..))` works before `Edmunds::decode_vin`.
+impl From<VinError> for Error {
+    fn from(e: VinError) -> Error {
+        Error::InvalidVin(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::status::StatusCode;
+
+    #[test]
+    fn mashery_over_qps() {
+        let error = from_response(StatusCode::Forbidden,
+                                  Some("ERR_403_DEVELOPER_OVER_QPS"),
+                                  "<h1>Developer Over Qps</h1>");
+
+        match error {
+            Error::OverQps(body) => {
+                assert_eq!("ERR_403_DEVELOPER_OVER_QPS", body.status);
+                assert_eq!("Developer Over Qps", body.message);
+            }
+            other => panic!("expected over qps, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mashery_over_rate() {
+        let error = from_response(StatusCode::Forbidden,
+                                  Some("ERR_403_DEVELOPER_OVER_RATE"),
+                                  "<h1>Developer Over Rate</h1>");
+
+        match error {
+            Error::OverQuota(_) => {}
+            other => panic!("expected over quota, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn inactive_key() {
+        let error = from_response(StatusCode::Forbidden,
+                                  Some("ERR_403_DEVELOPER_INACTIVE"),
+                                  "<h1>Developer Inactive</h1>");
+
+        match error {
+            Error::Unauthorized(StatusCode::Forbidden, _) => {}
+            other => panic!("expected unauthorized, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn server_error() {
+        let error = from_response(StatusCode::ServiceUnavailable, None, "");
+
+        match error {
+            Error::Server(StatusCode::ServiceUnavailable, _) => {}
+            other => panic!("expected server error, got {:?}", other),
+        }
+    }
+}
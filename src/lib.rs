@@ -2,128 +2,633 @@
 #![plugin(serde_macros)]
 
 extern crate hyper;
+extern crate rand;
 extern crate serde;
 extern crate serde_json;
+extern crate url;
+#[macro_use]
+extern crate log;
 
 mod models;
 pub use models::*;
 
-use hyper::{Client, Result};
-use hyper::client::Response;
-use hyper::header::Connection;
+mod error;
+pub use error::{Error, ApiError, Result};
 
-const BASE_URL: &'static str = "https://api.edmunds.com";
+mod rate_limit;
+pub use rate_limit::{RateLimiter, RateLimitStatus, LimitMode, Budget};
+
+mod retry;
+pub use retry::{RetryPolicy, RetryEvent, ErrorClass};
+
+mod builder;
+pub use builder::EdmundsBuilder;
+
+mod observer;
+pub use observer::{RequestEvent, RequestObserver, LogObserver};
+
+mod query;
+pub use query::Query;
 
-// const ALL_MAKES: &'static str = "/api/vehicle/v2/makes";
-// const MAKE_DETAIL: &'static str = "/api/vehicle/v2/{make}";
+use query::path_segment;
 
-// const MODELS_BY_MAKE: &'static str = "/api/vehicle/v2/{make}/models";
-// const MODEL_BY_MAKE_MODEL: &'static str = "/api/vehicle/v2/{make}/{model}";
+mod vin;
+pub use vin::{Vin, VinError, squish_vin};
 
-// const YEAR_BY_MAKE_MODEL: &'static str = "/api/vehicle/v2/{make}/{model}/years";
-// const YEAR_BY_MAKE_MODEL_YEAR: &'static str = "/api/vehicle/v2/{make}/{model}/{year}";
+mod tmv;
+pub use tmv::{NewTmv, TmvPrice, UsedTmv, UsedTmvPrice, Condition};
 
-// const STYLE_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}";
-// const STYLES_BY_MAKE_MODEL_YEAR: &'static str = "/api/vehicle/v2/{make}/{model}/{year}/styles";
-// const STYLE_BY_SQUISHVIN: &'static str = "/api/vehicle/v2/squishvins/{squishvin}";
+mod tco;
+pub use tco::{TrueCostToOwn, CostSchedule};
 
-// const OPTIONS_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}/options";
-// const OPTION_BY_OPTIONID: &'static str = "/api/vehicle/v2/options/{option_id}";
-// const COLORS_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}/colors";
-// const COLOR_BY_COLORID: &'static str = "/api/vehicle/v2/colors/{color_id}";
+mod maintenance;
+pub use maintenance::{MaintenanceAction, Frequency, LaborRate};
 
-// const ENGINES_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}/engines";
-// const ENGINE_BY_ENGINEID: &'static str = "/api/vehicle/v2/engines/{engine_id}";
-// const TRANSMISSIONS_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}/transmissions";
-// const TRANSMISSION_BY_TRANSMISSIONID: &'static str = "/api/vehicle/v2/transmissions/{transmission_id}";
+mod recall;
+pub use recall::{Recall, ServiceBulletin};
 
-// const EQUIPMENTS_BY_STYLEID: &'static str = "/api/vehicle/v2/styles/{style_id}/equipment";
-// const EQUIPMENT_BY_EQUIPMENTID: &'static str = "/api/vehicle/v2/equipment/{equipment_id}";
+mod content;
+pub use content::{EditorialReview, ConsumerReviews, ConsumerReview, CategoryRating};
 
+mod media;
+pub use media::{Photo, PhotoSource, PhotoCategory, ShotType};
+
+use std::io::Read;
+use std::thread;
+use std::time::Instant;
+
+use hyper::Client;
+use hyper::client::Response;
+use hyper::header::{Connection, ContentLength, Headers};
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+const BASE_URL: &'static str = "https://api.edmunds.com";
+const MEDIA_BASE_URL: &'static str = "https://media.ed.edmunds-media.com";
 
 pub struct Edmunds {
-    api_key: &'static str,
+    api_key: String,
+    base_url: String,
+    media_base_url: String,
     client: Client,
+    headers: Headers,
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    observer: Box<RequestObserver>,
 }
 
 impl Edmunds {
-    pub fn new(api_key: &'static str) -> Self {
-        Edmunds::with_client(api_key, Client::new())
+    /// A client with the default configuration, see `EdmundsBuilder` for the rest.
+    pub fn new<S: Into<String>>(api_key: S) -> Self {
+        EdmundsBuilder::new(api_key).build()
     }
 
-    pub fn call_send(&self, endpoint: &str, parameters: &str) -> Result<Response> {
-        let url = BASE_URL.to_string() + endpoint + "?fmt=json&api_key=" 
-                    + &self.api_key + "&" + parameters;
-
-        println!("{:?}", &url);
-
-        let request = self.client
-                          .get(&url)
-                          .header(Connection::close());
+    /// Remaining client-side budget for the configured rate limiter.
+    pub fn rate_limit_status(&self) -> RateLimitStatus {
+        self.rate_limiter.status()
+    }
 
-        request.send()
+    /// Sends a GET, retrying retryable failures according to the retry policy.
+    pub fn call_send(&self, endpoint: &str, query: &Query) -> Result<Response> {
+        let mut attempt = 1;
+
+        loop {
+            match self.send_once(endpoint, query) {
+                Err(ref error) if self.retry_policy.should_retry(error, attempt) => {
+                    let delay = self.retry_policy.delay(attempt);
+
+                    self.retry_policy.notify(&RetryEvent {
+                        endpoint: endpoint,
+                        attempt: attempt,
+                        error: error,
+                        delay: delay,
+                    });
+
+                    thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
-    pub fn call<T>(&self, endpoint: &str, parameters: &str) -> Result<T> 
-        where T: serde::de::Deserialize 
+    pub fn call<T>(&self, endpoint: &str, query: &Query) -> Result<T>
+        where T: serde::de::Deserialize
     {
-        let response = try!(self.call_send(endpoint, parameters));
+        let mut response = try!(self.call_send(endpoint, query));
 
-        let result = serde_json::from_reader(response)
-                                .expect("deserialization failed");
+        let mut body = String::new();
+        try!(response.read_to_string(&mut body));
 
-        Ok(result)
+        serde_json::from_str(&body).map_err(|e| error::json(endpoint, &body, e))
     }
 
-    pub fn all_makes(&self, state: State) -> Result<Vec<Make>> {
-        let parameters = "state=".to_string() + &format!("{:?}", state).to_lowercase();
-
-        let all_makes: AllMakes = try!(self.call("/api/vehicle/v2/makes", &parameters));
+    pub fn all_makes(&self, query: &Query) -> Result<Vec<Make>> {
+        let all_makes: AllMakes = try!(self.call("/api/vehicle/v2/makes", query));
 
         Ok(all_makes.makes)
     }
 
-    pub fn models_by_make(&self, make_nicename: &str) -> Result<Vec<Model>> {
-        let parameters = "state=new";
-        let url = format!("/api/vehicle/v2/{make}/models", make = make_nicename);
+    /// Number of makes matching `query`'s state and year.
+    pub fn makes_count(&self, query: &Query) -> Result<u32> {
+        let count: MakesCount = try!(self.call("/api/vehicle/v2/makes/count", query));
+
+        Ok(count.makes_count)
+    }
+
+    /// A single make with its models and years, filtered by `query`'s state and year.
+    pub fn make_details(&self, make_nicename: &str, query: &Query) -> Result<Make> {
+        let url = format!("/api/vehicle/v2/{make}", make = path_segment(make_nicename));
 
-        let all_models: AllModels = try!(self.call(&url, &parameters));
+        self.call(&url, query)
+    }
+
+    pub fn models_by_make(&self, make_nicename: &str, query: &Query) -> Result<Vec<Model>> {
+        let url = format!("/api/vehicle/v2/{make}/models", make = path_segment(make_nicename));
+
+        let all_models: AllModels = try!(self.call(&url, query));
 
         Ok(all_models.models)
     }
 
+    pub fn models_count(&self, make_nicename: &str, query: &Query) -> Result<u32> {
+        let url = format!("/api/vehicle/v2/{make}/models/count",
+                          make = path_segment(make_nicename));
+
+        let count: ModelsCount = try!(self.call(&url, query));
+
+        Ok(count.models_count)
+    }
+
+    /// A single model with its years, filtered by `query`'s state, submodel and category.
+    pub fn model_details(&self,
+                         make_nicename: &str,
+                         model_nicename: &str,
+                         query: &Query)
+                         -> Result<Model> {
+        let url = format!("/api/vehicle/v2/{make}/{model}",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename));
+
+        self.call(&url, query)
+    }
+
+    pub fn years_by_make_model(&self,
+                               make_nicename: &str,
+                               model_nicename: &str,
+                               query: &Query)
+                               -> Result<Vec<Year>> {
+        let url = format!("/api/vehicle/v2/{make}/{model}/years",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename));
+
+        let all_years: AllYears = try!(self.call(&url, query));
+
+        Ok(all_years.years)
+    }
+
+    pub fn years_count(&self,
+                       make_nicename: &str,
+                       model_nicename: &str,
+                       query: &Query)
+                       -> Result<u32> {
+        let url = format!("/api/vehicle/v2/{make}/{model}/years/count",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename));
+
+        let count: YearsCount = try!(self.call(&url, query));
+
+        Ok(count.years_count)
+    }
+
+    pub fn year_details(&self,
+                        make_nicename: &str,
+                        model_nicename: &str,
+                        year: u16,
+                        query: &Query)
+                        -> Result<Year> {
+        let url = format!("/api/vehicle/v2/{make}/{model}/{year}",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
+                          year = year);
+
+        self.call(&url, query)
+    }
+
+    /// Styles of a model year, as much detail as `query`'s view asks for.
     pub fn styles_by_make_model_year(&self,
                                      make_nicename: &str,
                                      model_nicename: &str,
-                                     year: u16)
-                                     -> Result<Vec<Style>> {
-        let parameters = "state=new&view=full";
+                                     year: u16,
+                                     query: &Query)
+                                     -> Result<Styles> {
         let url = format!("/api/vehicle/v2/{make}/{model}/{year}/styles",
-                          make = make_nicename,
-                          model = model_nicename,
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
+                          year = year);
+
+        self.call(&url, query)
+    }
+
+    /// Number of styles for a model year, without fetching the styles themselves.
+    pub fn styles_count(&self,
+                        make_nicename: &str,
+                        model_nicename: &str,
+                        year: u16,
+                        query: &Query)
+                        -> Result<u32> {
+        let url = format!("/api/vehicle/v2/{make}/{model}/{year}/styles/count",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
                           year = year);
 
-        let all_styles: AllStyles = try!(self.call(&url, &parameters));
+        let count: StylesCount = try!(self.call(&url, query));
+
+        Ok(count.styles_count)
+    }
+
+    pub fn style_by_id(&self, style_id: u32, view: View) -> Result<Style> {
+        let url = format!("/api/vehicle/v2/styles/{style_id}", style_id = style_id);
+
+        self.call(&url, &Query::new().view(view))
+    }
+
+    /// Styles matching a squish VIN, see `squish_vin` to derive one from a full VIN.
+    pub fn styles_by_squishvin(&self, squish_vin: &str) -> Result<Vec<Style>> {
+        let url = format!("/api/vehicle/v2/squishvins/{squish_vin}",
+                          squish_vin = path_segment(squish_vin));
+
+        let decoded: SquishVinDecode = try!(self.call(&url, &Query::new()));
+
+        let mut styles = vec![];
+        for year in decoded.years {
+            for style in year.styles {
+                styles.push((*style).clone());
+            }
+        }
+
+        Ok(styles)
+    }
+
+    /// Decodes a full 17 character VIN to its make, model, matching styles and equipment.
+    ///
+    /// Build the `Vin` with `Vin::parse`, or `Vin::parse_unchecked` for VINs
+    /// without a North American check digit, so a bad VIN costs no quota.
+    pub fn decode_vin(&self, vin: &Vin) -> Result<VinDecode> {
+        let url = format!("/api/vehicle/v2/vins/{vin}", vin = vin);
+
+        self.call(&url, &Query::new())
+    }
+
+    /// The options available on a style grouped by category, optionally only one category.
+    pub fn options_by_style_id(&self,
+                               style_id: u32,
+                               category: Option<OptionCategory>)
+                               -> Result<Vec<CarOption>> {
+        let url = format!("/api/vehicle/v2/styles/{style_id}/options", style_id = style_id);
 
-        Ok(all_styles.styles)
+        let mut query = Query::new();
+        if let Some(category) = category {
+            query = query.category(category.as_str());
+        }
+
+        let all_options: AllOptions = try!(self.call(&url, &query));
+
+        Ok(CarOption::group(all_options.options))
+    }
+
+    pub fn option_by_id(&self, option_id: &str) -> Result<CarOptionDetail> {
+        let url = format!("/api/vehicle/v2/options/{option_id}",
+                          option_id = path_segment(option_id));
+
+        self.call(&url, &Query::new())
+    }
+
+    /// The paint and upholstery colors of a style grouped by category, optionally only one.
+    pub fn colors_by_style_id(&self,
+                              style_id: u32,
+                              category: Option<ColorCategory>)
+                              -> Result<Vec<Color>> {
+        let url = format!("/api/vehicle/v2/styles/{style_id}/colors", style_id = style_id);
+
+        let mut query = Query::new();
+        if let Some(category) = category {
+            query = query.category(category.as_str());
+        }
+
+        let all_colors: AllColors = try!(self.call(&url, &query));
+
+        Ok(Color::group(all_colors.colors))
+    }
+
+    pub fn color_by_id(&self, color_id: &str) -> Result<ColorOption> {
+        let url = format!("/api/vehicle/v2/colors/{color_id}", color_id = path_segment(color_id));
+
+        self.call(&url, &Query::new())
+    }
+
+    /// The engines a style can be ordered with, optionally only standard or optional ones.
+    pub fn engines_by_style_id(&self,
+                               style_id: u32,
+                               availability: Option<Availability>)
+                               -> Result<Vec<Engine>> {
+        let url = format!("/api/vehicle/v2/styles/{style_id}/engines", style_id = style_id);
+
+        let mut query = Query::new();
+        if let Some(availability) = availability {
+            query = query.availability(availability);
+        }
+
+        let all_engines: AllEngines = try!(self.call(&url, &query));
+
+        Ok(all_engines.engines)
+    }
+
+    pub fn engine_by_id(&self, engine_id: &str) -> Result<Engine> {
+        let url = format!("/api/vehicle/v2/engines/{engine_id}",
+                          engine_id = path_segment(engine_id));
+
+        self.call(&url, &Query::new())
+    }
+
+    /// The transmissions a style can be ordered with, optionally only standard or optional ones.
+    pub fn transmissions_by_style_id(&self,
+                                     style_id: u32,
+                                     availability: Option<Availability>)
+                                     -> Result<Vec<Transmission>> {
+        let url = format!("/api/vehicle/v2/styles/{style_id}/transmissions",
+                          style_id = style_id);
+
+        let mut query = Query::new();
+        if let Some(availability) = availability {
+            query = query.availability(availability);
+        }
+
+        let all_transmissions: AllTransmissions = try!(self.call(&url, &query));
+
+        Ok(all_transmissions.transmissions)
+    }
+
+    pub fn transmission_by_id(&self, transmission_id: &str) -> Result<Transmission> {
+        let url = format!("/api/vehicle/v2/transmissions/{transmission_id}",
+                          transmission_id = path_segment(transmission_id));
+
+        self.call(&url, &Query::new())
     }
 
-    pub fn equipment_by_styleid(&self, style_id: u32) -> Result<Vec<Equipment>> {
+    /// Equipment for a style, filtered server-side by `Query::equipment_type` and
+    /// `Query::availability`.
+    pub fn equipment_by_styleid(&self, style_id: u32, query: &Query) -> Result<Vec<Equipment>> {
         let url = format!("/api/vehicle/v2/styles/{style_id}/equipment",
                           style_id = style_id);
 
-        let all_equipment: AllEquipment = try!(self.call(&url, &""));
+        let all_equipment: AllEquipment = try!(self.call(&url, query));
 
         Ok(all_equipment.equipment)
     }
 
+    pub fn equipment_by_id(&self, equipment_id: &str) -> Result<Equipment> {
+        let url = format!("/api/vehicle/v2/equipment/{equipment_id}",
+                          equipment_id = path_segment(equipment_id));
+
+        self.call(&url, &Query::new())
+    }
+
+    /// True Market Value of a new style built with `option_ids` and `color_id`, priced for `zip`.
+    pub fn new_tmv(&self,
+                   style_id: u32,
+                   zip: &str,
+                   option_ids: &[&str],
+                   color_id: Option<&str>)
+                   -> Result<NewTmv> {
+        let mut query = Query::new().param("styleid", style_id.to_string()).param("zip", zip);
+        for option_id in option_ids {
+            query = query.param("optionid", *option_id);
+        }
+        if let Some(color_id) = color_id {
+            query = query.param("colorid", color_id);
+        }
+
+        let new_tmv: NewTmvResponse =
+            try!(self.call("/v1/api/tmv/tmvservice/calculatenewtmv", &query));
+
+        Ok(new_tmv.tmv)
+    }
+
+    /// Trade-in, private party and dealer retail values of a used style, priced for `zip`.
+    pub fn used_tmv(&self,
+                    style_id: u32,
+                    zip: &str,
+                    condition: Condition,
+                    mileage: u32,
+                    option_ids: &[&str],
+                    color_id: Option<&str>)
+                    -> Result<UsedTmv> {
+        let mut query = Query::new()
+                            .param("styleid", style_id.to_string())
+                            .param("zip", zip)
+                            .param("condition", condition.as_str())
+                            .param("mileage", mileage.to_string());
+        for option_id in option_ids {
+            query = query.param("optionid", *option_id);
+        }
+        if let Some(color_id) = color_id {
+            query = query.param("colorid", color_id);
+        }
+
+        let used_tmv: UsedTmvResponse =
+            try!(self.call("/v1/api/tmv/tmvservice/calculateusedtmv", &query));
+
+        Ok(used_tmv.tmv)
+    }
+
+    /// Five years of ownership costs for a new style bought in `zip`.
+    pub fn true_cost_to_own(&self, style_id: u32, zip: &str) -> Result<TrueCostToOwn> {
+        let url = format!("/v1/api/tco/newtruecosttoownbystyleidandzip/{style_id}/{zip}",
+                          style_id = style_id,
+                          zip = path_segment(zip));
+
+        self.call(&url, &Query::new())
+    }
+
+    /// The maintenance schedule of a model year, keyed by `Year::id`.
+    pub fn maintenance_actions(&self, model_year_id: u32) -> Result<Vec<MaintenanceAction>> {
+        let query = Query::new().param("modelyearid", model_year_id.to_string());
+
+        let actions: ActionHolder =
+            try!(self.call("/v1/api/maintenance/actionrepository/findbymodelyearid", &query));
+
+        Ok(actions.action_holder)
+    }
+
+    /// Maintenance due at `mileage` for a model year, keyed by `Year::id`.
+    pub fn maintenance_actions_by_mileage(&self,
+                                          model_year_id: u32,
+                                          mileage: u32)
+                                          -> Result<Vec<MaintenanceAction>> {
+        let query = Query::new()
+                        .param("modelyearid", model_year_id.to_string())
+                        .param("mileage", mileage.to_string());
+
+        let actions: ActionHolder =
+            try!(self.call("/v1/api/maintenance/actionrepository/findbymodelyearidandmileage",
+                           &query));
+
+        Ok(actions.action_holder)
+    }
+
+    pub fn labor_rates_by_zip(&self, zip: &str) -> Result<Vec<LaborRate>> {
+        let query = Query::new().param("zip", zip);
+
+        let rates: ZipLaborRateHolder =
+            try!(self.call("/v1/api/maintenance/ziplaborrate/findbyzip", &query));
+
+        Ok(rates.zip_labor_rate_holder)
+    }
+
+    /// Safety recalls filed against a model year, keyed by `Year::id`.
+    pub fn recalls_by_model_year_id(&self, model_year_id: u32) -> Result<Vec<Recall>> {
+        let query = Query::new().param("modelyearid", model_year_id.to_string());
+
+        let recalls: RecallHolder =
+            try!(self.call("/v1/api/maintenance/recallrepository/findbymodelyearid", &query));
+
+        Ok(recalls.recall_holder)
+    }
+
+    /// Technical service bulletins issued for a model year, keyed by `Year::id`.
+    pub fn service_bulletins_by_model_year_id(&self,
+                                              model_year_id: u32)
+                                              -> Result<Vec<ServiceBulletin>> {
+        let query = Query::new().param("modelyearid", model_year_id.to_string());
+
+        let bulletins: ServiceBulletinHolder =
+            try!(self.call("/v1/api/maintenance/servicebulletinrepository/findbymodelyearid",
+                           &query));
+
+        Ok(bulletins.service_bulletin_holder)
+    }
+
+    /// Edmunds' review of a model year with its pros, cons and scores.
+    pub fn editorial_review(&self,
+                            make_nicename: &str,
+                            model_nicename: &str,
+                            year: u16)
+                            -> Result<EditorialReview> {
+        let url = format!("/api/editorial/v2/{make}/{model}/{year}",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
+                          year = year);
+
+        self.call(&url, &Query::new())
+    }
+
+    /// Owner ratings and a page of reviews for a model year, paged by `query`.
+    pub fn consumer_reviews(&self,
+                            make_nicename: &str,
+                            model_nicename: &str,
+                            year: u16,
+                            query: &Query)
+                            -> Result<ConsumerReviews> {
+        let url = format!("/api/vehiclereviews/v2/{make}/{model}/{year}",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
+                          year = year);
+
+        self.call(&url, query)
+    }
+
+    /// Owner ratings and a page of reviews for a single style, paged by `query`.
+    pub fn consumer_reviews_by_style_id(&self,
+                                        style_id: u32,
+                                        query: &Query)
+                                        -> Result<ConsumerReviews> {
+        let url = format!("/api/vehiclereviews/v2/styles/{style_id}", style_id = style_id);
+
+        self.call(&url, query)
+    }
+
+    /// Photos of a style, filtered by `query`'s photo category, shot type and width.
+    pub fn photos_by_style_id(&self, style_id: u32, query: &Query) -> Result<Vec<Photo>> {
+        let url = format!("/api/media/v2/styles/{style_id}/photos", style_id = style_id);
+
+        let all_photos: AllPhotos = try!(self.call(&url, query));
+
+        Ok(media::resolve(&self.media_base_url, all_photos.photos))
+    }
+
+    /// Photos of a model year, filtered by `query`'s photo category, shot type and width.
+    pub fn photos_by_make_model_year(&self,
+                                     make_nicename: &str,
+                                     model_nicename: &str,
+                                     year: u16,
+                                     query: &Query)
+                                     -> Result<Vec<Photo>> {
+        let url = format!("/api/media/v2/{make}/{model}/{year}/photos",
+                          make = path_segment(make_nicename),
+                          model = path_segment(model_nicename),
+                          year = year);
+
+        let all_photos: AllPhotos = try!(self.call(&url, query));
+
+        Ok(media::resolve(&self.media_base_url, all_photos.photos))
+    }
+
     // private fns
-    /// For testing pass a mock client
-    fn with_client(api_key: &'static str, client: Client) -> Self {
-        Edmunds {
-            api_key: api_key,
-            client: client,
+    fn send_once(&self, endpoint: &str, query: &Query) -> Result<Response> {
+        try!(self.rate_limiter.acquire());
+
+        let query = query.clone().param("api_key", self.api_key.clone()).to_string();
+        let url = self.base_url.clone() + endpoint + "?" + &query;
+
+        let started = Instant::now();
+
+        let request = self.client
+                          .get(&url)
+                          .headers(self.headers.clone())
+                          .header(Connection::close());
+
+        let mut response = match request.send() {
+            Ok(response) => response,
+            Err(e) => {
+                self.observe(endpoint, &query, started, None, None);
+                return Err(Error::Http(e));
+            }
+        };
+
+        // Observed before any body is read, so latency is time to headers.
+        let size = response.headers.get::<ContentLength>().map(|length| length.0);
+        self.observe(endpoint, &query, started, Some(response.status), size);
+
+        if response.status.is_success() {
+            return Ok(response);
         }
+
+        let mashery_code = response.headers
+                                   .get_raw("X-Mashery-Error-Code")
+                                   .and_then(|values| values.first())
+                                   .map(|value| String::from_utf8_lossy(value).into_owned());
+
+        let mut body = String::new();
+        try!(response.read_to_string(&mut body));
+
+        Err(error::from_response(response.status, mashery_code.as_ref().map(|c| &c[..]), &body))
+    }
+
+    fn observe(&self,
+               endpoint: &str,
+               query: &str,
+               started: Instant,
+               status: Option<StatusCode>,
+               response_size: Option<u64>) {
+        self.observer.on_request(&RequestEvent {
+            method: Method::Get,
+            endpoint: endpoint.to_string(),
+            query: observer::redact(query),
+            status: status,
+            latency: started.elapsed(),
+            response_size: response_size,
+        });
     }
 }
 
@@ -139,8 +644,97 @@ struct AllModels {
 }
 
 #[derive(Deserialize)]
-struct AllStyles {
-    styles: Vec<Style>
+struct AllYears {
+    years: Vec<Year>
+}
+
+#[derive(Deserialize)]
+struct MakesCount {
+    #[serde(rename="makesCount")]
+    makes_count: u32
+}
+
+#[derive(Deserialize)]
+struct ModelsCount {
+    #[serde(rename="modelsCount")]
+    models_count: u32
+}
+
+#[derive(Deserialize)]
+struct YearsCount {
+    #[serde(rename="yearsCount")]
+    years_count: u32
+}
+
+#[derive(Deserialize)]
+struct StylesCount {
+    #[serde(rename="stylesCount")]
+    styles_count: u32
+}
+
+#[derive(Deserialize)]
+struct SquishVinDecode {
+    years: Vec<Year>
+}
+
+#[derive(Deserialize)]
+struct AllOptions {
+    options: Vec<CarOptionDetail>
+}
+
+#[derive(Deserialize)]
+struct AllColors {
+    colors: Vec<ColorOption>
+}
+
+#[derive(Deserialize)]
+struct AllEngines {
+    engines: Vec<Engine>
+}
+
+#[derive(Deserialize)]
+struct AllTransmissions {
+    transmissions: Vec<Transmission>
+}
+
+#[derive(Deserialize)]
+struct NewTmvResponse {
+    tmv: NewTmv
+}
+
+#[derive(Deserialize)]
+struct UsedTmvResponse {
+    tmv: UsedTmv
+}
+
+#[derive(Deserialize)]
+struct ActionHolder {
+    #[serde(rename="actionHolder")]
+    action_holder: Vec<MaintenanceAction>
+}
+
+#[derive(Deserialize)]
+struct ZipLaborRateHolder {
+    #[serde(rename="zipLaborRateHolder")]
+    zip_labor_rate_holder: Vec<LaborRate>
+}
+
+#[derive(Deserialize)]
+struct RecallHolder {
+    #[serde(rename="recallHolder")]
+    recall_holder: Vec<Recall>
+}
+
+#[derive(Deserialize)]
+struct ServiceBulletinHolder {
+    #[serde(rename="serviceBulletinHolder")]
+    service_bulletin_holder: Vec<ServiceBulletin>
+}
+
+#[derive(Deserialize)]
+struct AllPhotos {
+    #[serde(default)]
+    photos: Vec<media::RawPhoto>
 }
 
 #[derive(Deserialize)]
@@ -156,15 +750,22 @@ struct AllEquipment {
 #[macro_use]
 mod hyper_mock;
 
-#[cfg(test)]
-#[macro_use]
-extern crate log;
-
 #[cfg(test)]
 mod tests {
     #![allow(unused_imports)]
     use super::*;
     use super::hyper;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Keeps every request event, to check the query a call actually sent.
+    struct Recorder(Arc<Mutex<Vec<RequestEvent>>>);
+
+    impl RequestObserver for Recorder {
+        fn on_request(&self, event: &RequestEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
 
     #[test]
     fn all_makes() {
@@ -175,9 +776,9 @@ mod tests {
 
         let client = hyper::Client::with_connector(MockAllMakes);
 
-        let ed = Edmunds::with_client("api_key", client);
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        let makes = ed.all_makes(State::New);
+        let makes = ed.all_makes(&Query::new().state(State::New));
 
         println!("{:?}", makes);
     }
@@ -191,9 +792,9 @@ mod tests {
 
         let client = hyper::Client::with_connector(MockAllBmwModels);
 
-        let ed = Edmunds::with_client("api_key", client);
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        let models = ed.models_by_make("bmw").unwrap();
+        let models = ed.models_by_make("bmw", &Query::new().state(State::New)).unwrap();
 
         assert_eq!(30, models.len());
 
@@ -201,74 +802,1009 @@ mod tests {
     }
 
     #[test]
-    fn alfa_romeo_4c_2015_styles_full() {
-        mock_connector!(MockStylesFull {
+    fn acura_make_details() {
+        mock_connector!(MockMakeDetails {
             "https://api.edmunds.com" =>
-                include_str!("../test-samples/http/alfa_romeo_4c_2015_styles_full.http")
+                include_str!("../test-samples/http/acura_make_details.http")
         });
 
-        let client = hyper::Client::with_connector(MockStylesFull);
-
-        let ed = Edmunds::with_client("api_key", client);
+        let client = hyper::Client::with_connector(MockMakeDetails);
 
-        let styles = ed.styles_by_make_model_year("alfa-romeo", "4c", 2015).unwrap();
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        assert_eq!(3, styles.len());
+        let make = ed.make_details("acura", &Query::new().state(State::New)).unwrap();
 
-        println!("{:?}", styles);
+        assert_eq!("Acura", make.name);
+        assert_eq!(9, make.models.len());
     }
 
     #[test]
-    fn acura_ilx_2014_styles_full() {
-        mock_connector!(MockStylesFull {
+    fn acura_ilx_model_details() {
+        mock_connector!(MockModelDetails {
             "https://api.edmunds.com" =>
-                include_str!("../test-samples/http/acura_ilx_2014_styles_full.http")
+                include_str!("../test-samples/http/acura_ilx_model_details.http")
         });
 
-        let client = hyper::Client::with_connector(MockStylesFull);
-
-        let ed = Edmunds::with_client("api_key", client);
+        let client = hyper::Client::with_connector(MockModelDetails);
 
-        let styles = ed.styles_by_make_model_year("acura", "ilx", 2014).unwrap();
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        assert_eq!(4, styles.len());
+        let model = ed.model_details("acura", "ilx", &Query::new().state(State::New)).unwrap();
 
-        println!("{:?}", styles);
+        assert_eq!("ILX", model.name);
+        assert_eq!(3, model.years.len());
+        assert_eq!(4, model.years[0].styles.len());
+        assert_eq!(0, model.years[1].styles.len());
     }
 
     #[test]
-    fn acura_ilx_2016_styles_full() {
-        mock_connector!(MockStylesFull {
+    fn acura_ilx_years() {
+        mock_connector!(MockYears {
             "https://api.edmunds.com" =>
-                include_str!("../test-samples/http/acura_ilx_2016_styles_full.http")
+                include_str!("../test-samples/http/acura_ilx_years.http")
         });
 
-        let client = hyper::Client::with_connector(MockStylesFull);
-
-        let ed = Edmunds::with_client("api_key", client);
+        let client = hyper::Client::with_connector(MockYears);
 
-        let styles = ed.styles_by_make_model_year("acura", "ilx", 2016).unwrap();
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        assert_eq!(6, styles.len());
+        let query = Query::new().state(State::New).submodel("sedan");
+        let years = ed.years_by_make_model("acura", "ilx", &query).unwrap();
 
-        println!("{:?}", styles);
+        assert_eq!(3, years.len());
+        assert_eq!(2016, years[2].year);
+        assert_eq!(6, years[2].styles.len());
     }
 
     #[test]
-    fn bmw_i3_2014_styles_full() {
-        mock_connector!(MockStylesFull {
+    fn acura_ilx_2014_year_details() {
+        mock_connector!(MockYearDetails {
             "https://api.edmunds.com" =>
-                include_str!("../test-samples/http/bmw_i3_2014_styles_full.http")
+                include_str!("../test-samples/http/acura_ilx_2014_year_details.http")
         });
 
-        let client = hyper::Client::with_connector(MockStylesFull);
+        let client = hyper::Client::with_connector(MockYearDetails);
 
-        let ed = Edmunds::with_client("api_key", client);
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
 
-        let styles = ed.styles_by_make_model_year("bmw", "i3", 2014).unwrap();
+        let year = ed.year_details("acura", "ilx", 2014, &Query::new()).unwrap();
 
-        assert_eq!(2, styles.len());
+        assert_eq!(200471908, year.id);
+        assert_eq!(4, year.styles.len());
+        assert_eq!("sedan", year.styles[0].submodel.nice_name);
+    }
 
-        println!("{:?}", styles);
+    #[test]
+    fn alfa_romeo_4c_2015_styles_full() {
+        mock_connector!(MockStylesFull {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/alfa_romeo_4c_2015_styles_full.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStylesFull);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let query = Query::new().state(State::New).view(View::Full);
+        let styles = ed.styles_by_make_model_year("alfa-romeo", "4c", 2015, &query).unwrap();
+
+        assert_eq!(3, styles.styles.len());
+        assert_eq!(3, styles.count);
+
+        println!("{:?}", styles);
+    }
+
+    #[test]
+    fn acura_ilx_2014_styles_full() {
+        mock_connector!(MockStylesFull {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2014_styles_full.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStylesFull);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let query = Query::new().state(State::New).view(View::Full);
+        let styles = ed.styles_by_make_model_year("acura", "ilx", 2014, &query).unwrap();
+
+        assert_eq!(4, styles.styles.len());
+        assert_eq!(4, styles.count);
+
+        println!("{:?}", styles);
+    }
+
+    #[test]
+    fn acura_ilx_2016_styles_full() {
+        mock_connector!(MockStylesFull {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2016_styles_full.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStylesFull);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let query = Query::new().state(State::New).view(View::Full);
+        let styles = ed.styles_by_make_model_year("acura", "ilx", 2016, &query).unwrap();
+
+        assert_eq!(6, styles.styles.len());
+        assert_eq!(6, styles.count);
+
+        println!("{:?}", styles);
+    }
+
+    #[test]
+    fn bmw_i3_2014_styles_full() {
+        mock_connector!(MockStylesFull {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/bmw_i3_2014_styles_full.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStylesFull);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let query = Query::new().state(State::New).view(View::Full);
+        let styles = ed.styles_by_make_model_year("bmw", "i3", 2014, &query).unwrap();
+
+        assert_eq!(2, styles.styles.len());
+        assert_eq!(2, styles.count);
+
+        println!("{:?}", styles);
+    }
+
+    #[test]
+    fn style_by_id_full() {
+        mock_connector!(MockStyleFull {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_full.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStyleFull);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let style = ed.style_by_id(200471910, View::Full).unwrap();
+
+        assert_eq!("Base", style.trim);
+        assert!(style.engine.is_some());
+        assert_eq!(vec!["19VDE1F3EE".to_string()], style.squishVins);
+    }
+
+    #[test]
+    fn style_by_id_basic() {
+        mock_connector!(MockStyleBasic {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_basic.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStyleBasic);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let style = ed.style_by_id(200471910, View::Basic).unwrap();
+
+        assert_eq!("4dr Sedan (2.0L 4cyl 5A)", style.name);
+        assert!(style.engine.is_none());
+        assert!(style.colors.is_empty());
+    }
+
+    #[test]
+    fn styles_by_squishvin() {
+        mock_connector!(MockSquishVin {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/squishvin_19VDE1F3EE.http")
+        });
+
+        let client = hyper::Client::with_connector(MockSquishVin);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let squish = squish_vin("19VDE1F3XEE008012").unwrap();
+        let styles = ed.styles_by_squishvin(&squish).unwrap();
+
+        assert_eq!(1, styles.len());
+        assert_eq!(200471910, styles[0].id);
+    }
+
+    #[test]
+    fn decode_vin() {
+        mock_connector!(MockVin {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/vin_WBY1Z2C51EV123456.http")
+        });
+
+        let client = hyper::Client::with_connector(MockVin);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let vin = Vin::parse("WBY1Z2C51EV123456").unwrap();
+        let decoded = ed.decode_vin(&vin).unwrap();
+
+        assert_eq!("BMW", decoded.make.name);
+        assert_eq!("i3", decoded.model.nice_name);
+        assert_eq!(1, decoded.styles().len());
+        assert_eq!(200689763, decoded.styles()[0].id);
+        assert_eq!("Electric Motor", decoded.engine.unwrap().name);
+        assert_eq!(5, decoded.options.len());
+        assert_eq!(2, decoded.colors.len());
+    }
+
+    #[test]
+    fn decode_vin_without_check_digit() {
+        mock_connector!(MockUncheckedVin {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/vin_WBY1Z2C51EV123456.http")
+        });
+
+        let client = hyper::Client::with_connector(MockUncheckedVin);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        assert!(Vin::parse("WBY1Z2C57EV123456").is_err());
+
+        let vin = Vin::parse_unchecked("WBY1Z2C57EV123456").unwrap();
+        let decoded = ed.decode_vin(&vin).unwrap();
+
+        assert_eq!("BMW", decoded.make.name);
+    }
+
+    #[test]
+    fn options_by_style_id() {
+        mock_connector!(MockOptions {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200689763_options.http")
+        });
+
+        let client = hyper::Client::with_connector(MockOptions);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let options = ed.options_by_style_id(200689763, None).unwrap();
+
+        assert_eq!(5, options.len());
+        assert_eq!("Interior", options[0].category);
+        assert_eq!(2, options[0].options.len());
+        assert_eq!(4, options[3].options.len());
+    }
+
+    #[test]
+    fn options_by_style_id_package() {
+        mock_connector!(MockPackages {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200689763_options_package.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .connector(MockPackages)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        let options = ed.options_by_style_id(200689763, Some(OptionCategory::Package)).unwrap();
+
+        assert_eq!(1, options.len());
+        assert_eq!("Package", options[0].category);
+        assert_eq!(4, options[0].options.len());
+
+        let events = events.lock().unwrap();
+        assert!(events[0].query.contains("category=Package"));
+    }
+
+    #[test]
+    fn option_by_id() {
+        mock_connector!(MockOption {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/option_200690015.http")
+        });
+
+        let client = hyper::Client::with_connector(MockOption);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let option = ed.option_by_id("200690015").unwrap();
+
+        assert_eq!("Harman/Kardon Premium Sound", option.name);
+        assert_eq!(Some("Interior".to_string()), option.category);
+        assert_eq!(800, option.price.unwrap().baseMSRP);
+    }
+
+    #[test]
+    fn colors_by_style_id() {
+        mock_connector!(MockColors {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200689763_colors.http")
+        });
+
+        let client = hyper::Client::with_connector(MockColors);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let colors = ed.colors_by_style_id(200689763, None).unwrap();
+
+        assert_eq!(2, colors.len());
+        assert_eq!("Interior", colors[0].category);
+        assert_eq!(3, colors[0].options.len());
+        assert_eq!(6, colors[1].options.len());
+    }
+
+    #[test]
+    fn colors_by_style_id_exterior() {
+        mock_connector!(MockExterior {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200689763_colors_exterior.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .connector(MockExterior)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        let colors = ed.colors_by_style_id(200689763, Some(ColorCategory::Exterior)).unwrap();
+
+        assert_eq!(1, colors.len());
+        assert_eq!("Exterior", colors[0].category);
+
+        let events = events.lock().unwrap();
+        assert!(events[0].query.contains("category=Exterior"));
+    }
+
+    #[test]
+    fn color_by_id() {
+        mock_connector!(MockColor {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/color_200690020.http")
+        });
+
+        let client = hyper::Client::with_connector(MockColor);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let color = ed.color_by_id("200690020").unwrap();
+
+        assert_eq!("Ionic Silver Metallic W/BMW I Frozen Blue Accent", color.name);
+        assert_eq!("C3C8CE", color.colorChips.unwrap().primary.unwrap().hex);
+    }
+
+    #[test]
+    fn engines_by_style_id() {
+        mock_connector!(MockEngines {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_engines.http")
+        });
+
+        let client = hyper::Client::with_connector(MockEngines);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let engines = ed.engines_by_style_id(200471910, Some(Availability::Standard)).unwrap();
+
+        assert_eq!(1, engines.len());
+        assert_eq!(Some("STANDARD".to_string()), engines[0].availability);
+        assert_eq!("4INAG2.0", engines[0].code);
+    }
+
+    #[test]
+    fn engines_by_style_id_optional() {
+        mock_connector!(MockNoEngines {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_engines_optional.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .connector(MockNoEngines)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        let engines = ed.engines_by_style_id(200471910, Some(Availability::Optional)).unwrap();
+
+        assert!(engines.is_empty());
+
+        let events = events.lock().unwrap();
+        assert!(events[0].query.contains("availability=optional"));
+    }
+
+    #[test]
+    fn engine_by_id() {
+        mock_connector!(MockEngine {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/engine_200471914.http")
+        });
+
+        let client = hyper::Client::with_connector(MockEngine);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let engine = ed.engine_by_id("200471914").unwrap();
+
+        assert_eq!("200471914", engine.id);
+        assert_eq!(Some(4), engine.cylinder);
+    }
+
+    #[test]
+    fn transmissions_by_style_id() {
+        mock_connector!(MockTransmissions {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_transmissions.http")
+        });
+
+        let client = hyper::Client::with_connector(MockTransmissions);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let transmissions = ed.transmissions_by_style_id(200471910, None).unwrap();
+
+        assert_eq!(1, transmissions.len());
+        assert_eq!("AUTOMATIC", transmissions[0].transmissionType);
+    }
+
+    #[test]
+    fn transmission_by_id() {
+        mock_connector!(MockTransmission {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/transmission_200471916.http")
+        });
+
+        let client = hyper::Client::with_connector(MockTransmission);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let transmission = ed.transmission_by_id("200471916").unwrap();
+
+        assert_eq!("5A", transmission.name);
+        assert_eq!("5", transmission.numberOfSpeeds);
+    }
+
+    #[test]
+    fn equipment_by_styleid_filtered() {
+        mock_connector!(MockAudio {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_equipment_audio.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .connector(MockAudio)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        let query = Query::new()
+                        .equipment_type(EquipmentType::AudioSystem)
+                        .availability(Availability::Standard);
+        let equipment = ed.equipment_by_styleid(200471910, &query).unwrap();
+
+        assert_eq!(1, equipment.len());
+        assert_eq!("AUDIO_SYSTEM", equipment[0].equipmentType);
+        assert_eq!(Some(7), equipment[0].get_attr::<u32>("Total Number Of Speakers"));
+
+        let events = events.lock().unwrap();
+        assert!(events[0].query.contains("equipmentType=AUDIO_SYSTEM"));
+        assert!(events[0].query.contains("availability=standard"));
+    }
+
+    #[test]
+    fn equipment_by_id() {
+        mock_connector!(MockMirrors {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/equipment_200471940.http")
+        });
+
+        let client = hyper::Client::with_connector(MockMirrors);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let equipment = ed.equipment_by_id("200471940").unwrap();
+
+        assert_eq!("MIRRORS", equipment.equipmentType);
+        assert_eq!("STANDARD", equipment.availability);
+        assert_eq!(Some("power".to_string()), equipment.get_attr("Power Driver Side Mirror"));
+    }
+
+    #[test]
+    fn makes_count() {
+        mock_connector!(MockMakesCount {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/makes_count_new.http")
+        });
+
+        let client = hyper::Client::with_connector(MockMakesCount);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        assert_eq!(43, ed.makes_count(&Query::new().state(State::New)).unwrap());
+    }
+
+    #[test]
+    fn models_count() {
+        mock_connector!(MockModelsCount {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_models_count.http")
+        });
+
+        let client = hyper::Client::with_connector(MockModelsCount);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        assert_eq!(9, ed.models_count("acura", &Query::new()).unwrap());
+    }
+
+    #[test]
+    fn years_count() {
+        mock_connector!(MockYearsCount {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_years_count.http")
+        });
+
+        let client = hyper::Client::with_connector(MockYearsCount);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        assert_eq!(3, ed.years_count("acura", "ilx", &Query::new()).unwrap());
+    }
+
+    #[test]
+    fn styles_count() {
+        mock_connector!(MockStylesCount {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2014_styles_count.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStylesCount);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        assert_eq!(4, ed.styles_count("acura", "ilx", 2014, &Query::new()).unwrap());
+    }
+
+    #[test]
+    fn new_tmv() {
+        mock_connector!(MockNewTmv {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/new_tmv_200689763.http")
+        });
+
+        let client = hyper::Client::with_connector(MockNewTmv);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let tmv = ed.new_tmv(200689763, "90210", &["200690015"], Some("200690020")).unwrap();
+
+        assert_eq!(41350.0, tmv.base.msrp);
+        assert_eq!(-215.0, tmv.regional_adjustment.tmv);
+        assert_eq!(764.0, tmv.options["200690015"].tmv);
+        assert_eq!(Some(500.0), tmv.color.as_ref().map(|c| c.invoice));
+        assert_eq!(950.0, tmv.destination);
+
+        let options_tmv = tmv.options.values().fold(0.0, |sum, o| sum + o.tmv);
+        let color_tmv = tmv.color.as_ref().map_or(0.0, |c| c.tmv);
+        assert_eq!(tmv.total.tmv,
+                   tmv.base.tmv + tmv.regional_adjustment.tmv + options_tmv + color_tmv +
+                   tmv.destination);
+    }
+
+    #[test]
+    fn used_tmv() {
+        mock_connector!(MockUsedTmv {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/used_tmv_200689763.http")
+        });
+
+        let client = hyper::Client::with_connector(MockUsedTmv);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let tmv = ed.used_tmv(200689763, "90210", Condition::Clean, 30000, &["200690015"], None)
+                    .unwrap();
+
+        assert_eq!(23076.0, tmv.base.trade_in);
+        assert_eq!(380.0, tmv.options["200690015"].private_party);
+        assert_eq!(350.0, tmv.mileage_adjustment.dealer_retail);
+        assert_eq!(-540.0, tmv.condition_adjustment.trade_in);
+        assert_eq!(27068.0, tmv.total.dealer_retail);
+        assert_eq!(25520.0, tmv.total.private_party);
+        assert_eq!(23306.0, tmv.total.trade_in);
+    }
+
+    #[test]
+    fn true_cost_to_own() {
+        mock_connector!(MockTco {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/tco_200689763_90210.http")
+        });
+
+        let client = hyper::Client::with_connector(MockTco);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let tco = ed.true_cost_to_own(200689763, "90210").unwrap();
+
+        assert_eq!(vec![12904.0, 4188.0, 3712.0, 3291.0, 2918.0], tco.depreciation.values);
+        assert_eq!(27013.0, tco.depreciation.total);
+        assert_eq!(1014.0, tco.repairs.total);
+        assert_eq!(51726.0, tco.total.total);
+
+        for year in 0..5 {
+            let costs = [&tco.depreciation, &tco.financing, &tco.insurance, &tco.taxes_and_fees,
+                         &tco.fuel, &tco.maintenance, &tco.repairs];
+            let sum = costs.iter().fold(0.0, |sum, cost| sum + cost.values[year]);
+            assert_eq!(tco.total.values[year], sum);
+        }
+    }
+
+    #[test]
+    fn maintenance_actions() {
+        mock_connector!(MockMaintenance {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/maintenance_200471908.http")
+        });
+
+        let client = hyper::Client::with_connector(MockMaintenance);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let actions = ed.maintenance_actions(200471908).unwrap();
+
+        assert_eq!(6, actions.len());
+        assert_eq!(Frequency::Once, actions[0].frequency);
+        assert_eq!("Engine Oil", actions[1].item);
+        assert_eq!(7500, actions[1].interval_miles);
+        assert_eq!(6, actions[1].interval_months);
+        assert_eq!(Frequency::EveryInterval, actions[1].frequency);
+        assert_eq!(26.0, actions[1].part_cost());
+    }
+
+    #[test]
+    fn maintenance_actions_by_mileage() {
+        mock_connector!(MockMaintenanceMileage {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/maintenance_200471908_30000.http")
+        });
+
+        let client = hyper::Client::with_connector(MockMaintenanceMileage);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let actions = ed.maintenance_actions_by_mileage(200471908, 30000).unwrap();
+
+        let items: Vec<&str> = actions.iter().map(|a| &a.item[..]).collect();
+        assert_eq!(vec!["Engine Oil", "Tires", "Cabin Air Filter", "Air Filter"], items);
+        assert!(actions.iter().all(|a| a.is_due_at(30000, 0)));
+    }
+
+    #[test]
+    fn labor_rates_by_zip() {
+        mock_connector!(MockLaborRate {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/labor_rate_90210.http")
+        });
+
+        let client = hyper::Client::with_connector(MockLaborRate);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let rates = ed.labor_rates_by_zip("90210").unwrap();
+
+        assert_eq!("90210", rates[0].zip);
+        assert_eq!(131.0, rates[0].labor_rate);
+    }
+
+    #[test]
+    fn recalls_by_model_year_id() {
+        mock_connector!(MockRecalls {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/recalls_200471908.http")
+        });
+
+        let client = hyper::Client::with_connector(MockRecalls);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let recalls = ed.recalls_by_model_year_id(200471908).unwrap();
+
+        assert_eq!(1, recalls.len());
+        assert_eq!("14V086000", recalls[0].campaign_number);
+        assert_eq!("STEERING", recalls[0].component);
+        assert_eq!("02/20/2014", recalls[0].date);
+        assert!(recalls[0].remedy.contains("software"));
+    }
+
+    #[test]
+    fn service_bulletins_by_model_year_id() {
+        mock_connector!(MockBulletins {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/service_bulletins_200471908.http")
+        });
+
+        let client = hyper::Client::with_connector(MockBulletins);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let bulletins = ed.service_bulletins_by_model_year_id(200471908).unwrap();
+
+        assert_eq!(2, bulletins.len());
+        assert_eq!("14-017", bulletins[1].bulletin_number);
+        assert_eq!("POWER TRAIN", bulletins[1].component);
+    }
+
+    #[test]
+    fn editorial_review() {
+        mock_connector!(MockEditorial {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2014_editorial.http")
+        });
+
+        let client = hyper::Client::with_connector(MockEditorial);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let review = ed.editorial_review("acura", "ilx", 2014).unwrap();
+
+        assert_eq!(Some(7.6), review.rating);
+        assert_eq!(3, review.pros.len());
+        assert_eq!("Small trunk", review.cons[2]);
+        assert_eq!(CategoryRating { title: "Comfort".to_string(), value: 8.1 },
+                   review.ratings[1]);
+    }
+
+    #[test]
+    fn consumer_reviews() {
+        mock_connector!(MockConsumerReviews {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2014_consumer_reviews.http")
+        });
+
+        let client = hyper::Client::with_connector(MockConsumerReviews);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let query = Query::new().page_num(1).page_size(2);
+        let reviews = ed.consumer_reviews("acura", "ilx", 2014, &query).unwrap();
+
+        assert_eq!(4.4, reviews.average_rating);
+        assert_eq!(23, reviews.reviews_count);
+        assert_eq!(4, reviews.ratings.len());
+        assert_eq!(2, reviews.reviews.len());
+        assert_eq!(Some("sfdriver".to_string()), reviews.reviews[0].author);
+        assert_eq!(5.0, reviews.reviews[0].rating);
+    }
+
+    #[test]
+    fn consumer_reviews_by_style_id() {
+        mock_connector!(MockStyleReviews {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_consumer_reviews.http")
+        });
+
+        let client = hyper::Client::with_connector(MockStyleReviews);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        let reviews = ed.consumer_reviews_by_style_id(200471910, &Query::new()).unwrap();
+
+        assert_eq!(11, reviews.reviews_count);
+        assert_eq!("Nice car, needs more power", reviews.reviews[0].title);
+        assert_eq!(Some("More rear legroom".to_string()),
+                   reviews.reviews[0].suggested_improvements);
+    }
+
+    #[test]
+    fn photos_by_style_id() {
+        mock_connector!(MockPhotos {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/style_200471910_photos_exterior.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .connector(MockPhotos)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        let query = Query::new()
+                        .photo_category(PhotoCategory::Exterior)
+                        .shot_type(ShotType::FrontQuarter);
+        let photos = ed.photos_by_style_id(200471910, &query).unwrap();
+
+        assert_eq!(1, photos.len());
+        assert_eq!("FQ", photos[0].shot_type);
+        assert_eq!("https://media.ed.edmunds-media.com/acura/ilx/2014/oem/\
+                    2014_acura_ilx_sedan_20-premium_fq_oem_1_815.jpg",
+                   photos[0].largest().unwrap().url);
+        assert_eq!(400, photos[0].closest_to_width(500).unwrap().width);
+
+        let events = events.lock().unwrap();
+        assert!(events[0].query.contains("category=EXTERIOR"));
+        assert!(events[0].query.contains("shottype=FQ"));
+    }
+
+    #[test]
+    fn photos_by_make_model_year() {
+        mock_connector!(MockYearPhotos {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/acura_ilx_2014_photos.http")
+        });
+
+        let client = hyper::Client::with_connector(MockYearPhotos);
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .client(client)
+                     .media_base_url("http://media.example.com/")
+                     .build();
+
+        let photos = ed.photos_by_make_model_year("acura", "ilx", 2014, &Query::new().width(150))
+                       .unwrap();
+
+        assert_eq!(2, photos.len());
+        assert_eq!("INTERIOR", photos[1].category);
+        assert_eq!("http://media.example.com/acura/ilx/2014/oem/\
+                    2014_acura_ilx_sedan_20-premium_d_oem_1_150.jpg",
+                   photos[1].sources[0].url);
+    }
+
+    #[test]
+    fn custom_base_url() {
+        mock_connector!(MockLocal {
+            "http://localhost" =>
+                include_str!("../test-samples/http/all_bmw_models_basic.http")
+        });
+
+        let ed = EdmundsBuilder::new("api_key".to_string())
+                     .base_url("http://localhost:8080/")
+                     .connector(MockLocal)
+                     .user_agent("edmunds-rs tests")
+                     .timeout(Duration::from_secs(5))
+                     .build();
+
+        let models = ed.models_by_make("bmw", &Query::new().state(State::New)).unwrap();
+
+        assert_eq!(30, models.len());
+    }
+
+    #[test]
+    fn observer_sees_redacted_query() {
+        mock_connector!(MockAllBmwModels {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/all_bmw_models_basic.http")
+        });
+
+        let events = Arc::new(Mutex::new(vec![]));
+
+        let ed = EdmundsBuilder::new("s3cr3t")
+                     .connector(MockAllBmwModels)
+                     .observer(Recorder(events.clone()))
+                     .build();
+
+        ed.models_by_make("bmw", &Query::new().state(State::New)).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(1, events.len());
+        assert_eq!("/api/vehicle/v2/bmw/models", events[0].endpoint);
+        assert_eq!(Some(hyper::Ok), events[0].status);
+        assert!(!events[0].query.contains("s3cr3t"));
+        assert!(events[0].query.contains("api_key=REDACTED"));
+    }
+
+    #[test]
+    fn unknown_make_is_not_found() {
+        mock_connector!(MockNotFound {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/unknown_make_not_found.http")
+        });
+
+        let client = hyper::Client::with_connector(MockNotFound);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        match ed.models_by_make("notamake", &Query::new()) {
+            Err(Error::NotFound(body)) => {
+                assert_eq!("NOT_FOUND", body.status);
+            }
+            other => panic!("expected not found, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn over_qps_is_reported() {
+        mock_connector!(MockOverQps {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/over_qps.http")
+        });
+
+        let client = hyper::Client::with_connector(MockOverQps);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        match ed.styles_by_make_model_year("acura", "ilx", 2014, &Query::new()) {
+            Err(Error::OverQps(body)) => {
+                assert_eq!("Account Over Queries Per Second Limit", body.message);
+            }
+            other => panic!("expected over qps, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn daily_budget_stops_requests() {
+        mock_connector!(MockAllMakes {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/all_makes_basic.http")
+        });
+
+        let client = hyper::Client::with_connector(MockAllMakes);
+
+        let ed = EdmundsBuilder::new("api_key")
+                     .client(client)
+                     .rate_limiter(RateLimiter::new(LimitMode::FailFast).per_day(1))
+                     .build();
+
+        ed.all_makes(&Query::new().state(State::New)).unwrap();
+
+        match ed.all_makes(&Query::new().state(State::New)) {
+            Err(Error::RateLimited(Budget::PerDay)) => {}
+            other => panic!("expected rate limited, got {:?}", other),
+        }
+
+        assert_eq!(Some(0), ed.rate_limit_status().remaining_today);
+    }
+
+    #[test]
+    fn over_qps_is_retried() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        mock_connector!(MockOverQps {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/over_qps.http")
+        });
+
+        let client = hyper::Client::with_connector(MockOverQps);
+
+        let retries = Arc::new(AtomicUsize::new(0));
+        let counter = retries.clone();
+
+        let policy = RetryPolicy::new()
+                         .max_attempts(3)
+                         .base_delay(Duration::from_millis(1))
+                         .on_retry(move |event| {
+                             assert_eq!(Some(ErrorClass::OverQps), event.error.class());
+                             counter.fetch_add(1, Ordering::SeqCst);
+                         });
+
+        let ed = EdmundsBuilder::new("api_key").client(client).retry_policy(policy).build();
+
+        match ed.models_by_make("bmw", &Query::new().state(State::New)) {
+            Err(Error::OverQps(_)) => {}
+            other => panic!("expected over qps, got {:?}", other),
+        }
+
+        assert_eq!(2, retries.load(Ordering::SeqCst));
+        assert_eq!(3, ed.rate_limit_status().used_today);
+    }
+
+    #[test]
+    fn malformed_body_is_json_error() {
+        mock_connector!(MockMalformed {
+            "https://api.edmunds.com" =>
+                include_str!("../test-samples/http/malformed_makes.http")
+        });
+
+        let client = hyper::Client::with_connector(MockMalformed);
+
+        let ed = EdmundsBuilder::new("api_key").client(client).build();
+
+        match ed.all_makes(&Query::new().state(State::New)) {
+            Err(Error::Json { endpoint, snippet, .. }) => {
+                assert_eq!("/api/vehicle/v2/makes", endpoint);
+                assert!(snippet.starts_with("{\"makes\":"));
+            }
+            other => panic!("expected json error, got {:?}", other),
+        }
     }
 }
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// How often a `MaintenanceAction` recurs, from the API's numeric `frequency` code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    /// Code 3: done once, at the interval.
+    Once,
+    /// Code 4: repeated every interval.
+    EveryInterval,
+    /// A code this client doesn't know.
+    Other(u32),
+}
+
+impl Frequency {
+    pub fn from_code(code: u32) -> Frequency {
+        match code {
+            3 => Frequency::Once,
+            4 => Frequency::EveryInterval,
+            code => Frequency::Other(code),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match *self {
+            Frequency::Once => 3,
+            Frequency::EveryInterval => 4,
+            Frequency::Other(code) => code,
+        }
+    }
+}
+
+impl Serialize for Frequency {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        self.code().serialize(serializer)
+    }
+}
+
+impl Deserialize for Frequency {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Frequency, D::Error>
+        where D: Deserializer
+    {
+        let code = try!(u32::deserialize(deserializer));
+        Ok(Frequency::from_code(code))
+    }
+}
+
+/// A manufacturer-scheduled service, e.g. replacing the engine oil every 7,500 miles.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaintenanceAction {
+    pub id: u32,
+    #[serde(rename="engineCode")]
+    pub engine_code: Option<String>,
+    #[serde(rename="transmissionCode")]
+    pub transmission_code: Option<String>,
+    #[serde(rename="intervalMileage")]
+    pub interval_miles: u32,
+    #[serde(rename="intervalMonth")]
+    pub interval_months: u32,
+    pub frequency: Frequency,
+    pub action: String,
+    pub item: String,
+    #[serde(rename="itemDescription")]
+    pub item_description: Option<String>,
+    #[serde(rename="laborUnits")]
+    pub labor_hours: f64,
+    #[serde(rename="partUnits")]
+    pub part_units: f64,
+    #[serde(rename="partCostPerUnit")]
+    pub part_cost_per_unit: f64,
+}
+
+impl MaintenanceAction {
+    /// Cost of the parts for one service.
+    pub fn part_cost(&self) -> f64 {
+        self.part_units * self.part_cost_per_unit
+    }
+
+    /// Whether the service falls due at `mileage` or `months` in service, whichever
+    /// the schedule reaches; pass 0 to leave either out.
+    ///
+    /// Actions with an unknown frequency are never reported as due.
+    pub fn is_due_at(&self, mileage: u32, months: u32) -> bool {
+        let reaches = |value: u32, interval: u32| {
+            if value == 0 || interval == 0 {
+                return false;
+            }
+
+            match self.frequency {
+                Frequency::Once => value == interval,
+                Frequency::EveryInterval => value % interval == 0,
+                Frequency::Other(_) => false,
+            }
+        };
+
+        reaches(mileage, self.interval_miles) || reaches(months, self.interval_months)
+    }
+}
+
+/// Hourly labor rate charged by service shops around a zip code.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LaborRate {
+    pub zip: String,
+    #[serde(rename="laborRate")]
+    pub labor_rate: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oil_change() -> MaintenanceAction {
+        MaintenanceAction {
+            id: 1,
+            engine_code: None,
+            transmission_code: None,
+            interval_miles: 7500,
+            interval_months: 6,
+            frequency: Frequency::EveryInterval,
+            action: "Replace".to_string(),
+            item: "Engine Oil".to_string(),
+            item_description: None,
+            labor_hours: 0.3,
+            part_units: 4.0,
+            part_cost_per_unit: 6.5,
+        }
+    }
+
+    #[test]
+    fn part_cost() {
+        assert_eq!(26.0, oil_change().part_cost());
+    }
+
+    #[test]
+    fn every_interval_is_due_at_each_multiple() {
+        let action = oil_change();
+
+        assert!(!action.is_due_at(0, 0));
+        assert!(action.is_due_at(7500, 0));
+        assert!(action.is_due_at(30000, 0));
+        assert!(!action.is_due_at(10000, 0));
+        assert!(action.is_due_at(10000, 12));
+        assert!(!action.is_due_at(0, 8));
+    }
+
+    #[test]
+    fn once_is_due_only_at_its_interval() {
+        let action = MaintenanceAction {
+            frequency: Frequency::Once,
+            interval_miles: 1000,
+            interval_months: 1,
+            ..oil_change()
+        };
+
+        assert!(action.is_due_at(1000, 0));
+        assert!(!action.is_due_at(2000, 0));
+        assert!(action.is_due_at(0, 1));
+        assert!(!action.is_due_at(0, 2));
+    }
+
+    #[test]
+    fn unknown_frequency_is_never_due() {
+        let action = MaintenanceAction { frequency: Frequency::Other(9), ..oil_change() };
+
+        assert!(!action.is_due_at(7500, 6));
+    }
+
+    #[test]
+    fn frequency_codes_round_trip() {
+        for code in 0..10 {
+            assert_eq!(code, Frequency::from_code(code).code());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+/// Which side of the vehicle a photo shows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhotoCategory {
+    Exterior,
+    Interior,
+}
+
+impl PhotoCategory {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            PhotoCategory::Exterior => "EXTERIOR",
+            PhotoCategory::Interior => "INTERIOR",
+        }
+    }
+}
+
+/// The angle a photo was taken from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShotType {
+    FrontQuarter,
+    RearQuarter,
+    Front,
+    Rear,
+    Side,
+    Dashboard,
+    Interior,
+    Engine,
+    Cargo,
+}
+
+impl ShotType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ShotType::FrontQuarter => "FQ",
+            ShotType::RearQuarter => "RQ",
+            ShotType::Front => "F",
+            ShotType::Rear => "R",
+            ShotType::Side => "S",
+            ShotType::Dashboard => "D",
+            ShotType::Interior => "I",
+            ShotType::Engine => "E",
+            ShotType::Cargo => "CC",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Photo {
+    pub title: Option<String>,
+    pub category: String,
+    #[serde(rename="shotTypeAbbreviation")]
+    pub shot_type: String,
+    pub provider: Option<String>,
+    pub color: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The same photo at each width it is published in.
+    pub sources: Vec<PhotoSource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PhotoSource {
+    /// Absolute URL on the media host.
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Photo {
+    pub fn largest(&self) -> Option<&PhotoSource> {
+        self.sources.iter().max_by_key(|s| s.width)
+    }
+
+    /// The source closest to `width` pixels wide.
+    pub fn closest_to_width(&self, width: u32) -> Option<&PhotoSource> {
+        self.sources.iter().min_by_key(|s| (s.width as i64 - width as i64).abs())
+    }
+}
+
+/// A photo as the API returns it, with links relative to the media host.
+#[derive(Deserialize)]
+pub struct RawPhoto {
+    title: Option<String>,
+    category: String,
+    #[serde(rename="shotTypeAbbreviation")]
+    shot_type: String,
+    provider: Option<String>,
+    color: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    sources: Vec<RawSource>,
+}
+
+#[derive(Deserialize)]
+struct RawSource {
+    link: Link,
+    size: Size,
+}
+
+#[derive(Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct Size {
+    width: u32,
+    height: u32,
+}
+
+/// Resolves every source link of `photos` against `media_base_url`.
+pub fn resolve(media_base_url: &str, photos: Vec<RawPhoto>) -> Vec<Photo> {
+    photos.into_iter()
+          .map(|photo| {
+              Photo {
+                  title: photo.title,
+                  category: photo.category,
+                  shot_type: photo.shot_type,
+                  provider: photo.provider,
+                  color: photo.color,
+                  tags: photo.tags,
+                  sources: photo.sources
+                                .into_iter()
+                                .map(|source| {
+                                    PhotoSource {
+                                        url: absolute_url(media_base_url, &source.link.href),
+                                        width: source.size.width,
+                                        height: source.size.height,
+                                    }
+                                })
+                                .collect(),
+              }
+          })
+          .collect()
+}
+
+fn absolute_url(media_base_url: &str, href: &str) -> String {
+    if href.starts_with("http://") || href.starts_with("https://") {
+        href.to_string()
+    } else if href.starts_with('/') {
+        format!("{}{}", media_base_url, href)
+    } else {
+        format!("{}/{}", media_base_url, href)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::absolute_url;
+
+    #[test]
+    fn absolute_urls() {
+        let base = "https://media.ed.edmunds-media.com";
+
+        assert_eq!("https://media.ed.edmunds-media.com/acura/ilx/2014/fq_150.jpg",
+                   absolute_url(base, "/acura/ilx/2014/fq_150.jpg"));
+        assert_eq!("https://media.ed.edmunds-media.com/acura/ilx/2014/fq_150.jpg",
+                   absolute_url(base, "acura/ilx/2014/fq_150.jpg"));
+        assert_eq!("http://cdn.example.com/fq.jpg",
+                   absolute_url(base, "http://cdn.example.com/fq.jpg"));
+    }
+}
@@ -1,19 +1,65 @@
-use super::Price;
+use super::{group_by_category, Price};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CarOption {
-    category: String,
+    pub category: String,
     #[serde(default)]
-    options: Vec<CarOptionDetail>,
+    pub options: Vec<CarOptionDetail>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CarOptionDetail {
-    id: String,
-    name: String,
-    description: Option<String>,
-    equipmentType: String,
-    price: Option<Price>,
-    manufactureOptionName: Option<String>,
-    manufactureOptionCode: Option<String>,
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub equipmentType: String,
+    pub availability: Option<String>,
+    /// E.g. `Interior`; `CarOption::group` groups by it, under `Other` when missing.
+    pub category: Option<String>,
+    pub price: Option<Price>,
+    pub manufactureOptionName: Option<String>,
+    pub manufactureOptionCode: Option<String>,
+}
+
+impl CarOption {
+    /// Groups options by their `category`, see `group_by_category`.
+    pub fn group(details: Vec<CarOptionDetail>) -> Vec<CarOption> {
+        group_by_category(details, |detail| detail.category.clone())
+            .into_iter()
+            .map(|(category, options)| {
+                CarOption {
+                    category: category,
+                    options: options,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The `category` filter for `Edmunds::options_by_style_id`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionCategory {
+    Interior,
+    Exterior,
+    Roof,
+    InteriorTrim,
+    Mechanical,
+    Package,
+    AdditionalFees,
+    Other,
+}
+
+impl OptionCategory {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            OptionCategory::Interior => "Interior",
+            OptionCategory::Exterior => "Exterior",
+            OptionCategory::Roof => "Roof",
+            OptionCategory::InteriorTrim => "Interior Trim",
+            OptionCategory::Mechanical => "Mechanical",
+            OptionCategory::Package => "Package",
+            OptionCategory::AdditionalFees => "Additional Fees",
+            OptionCategory::Other => "Other",
+        }
+    }
 }
@@ -1,40 +1,73 @@
-use super::Price;
+use super::{group_by_category, Price};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Color {
-    category: String,
-    options: Vec<ColorOption>,
+    pub category: String,
+    pub options: Vec<ColorOption>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ColorOption {
-    id: String,
-    name: String,
-    equipmentType: String,
-    price: Option<Price>,
-    manufactureOptionName: String,
-    manufactureOptionCode: String,
-    colorChips: Option<ColorChips>,
+    pub id: String,
+    pub name: String,
+    pub equipmentType: String,
+    /// `Interior` or `Exterior`; `Color::group` groups by it.
+    pub category: Option<String>,
+    pub price: Option<Price>,
+    pub manufactureOptionName: String,
+    pub manufactureOptionCode: String,
+    pub colorChips: Option<ColorChips>,
     #[serde(default)]
-    fabricTypes: Vec<FabricType>,
+    pub fabricTypes: Vec<FabricType>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ColorDetail {
-    r: u16,
-    g: u16,
-    b: u16,
-    hex: String,
+    pub r: u16,
+    pub g: u16,
+    pub b: u16,
+    pub hex: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ColorChips {
-    primary: Option<ColorDetail>,
-    secondary: Option<ColorDetail>,
+    pub primary: Option<ColorDetail>,
+    pub secondary: Option<ColorDetail>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FabricType {
-    name: String,
-    value: String,
+    pub name: String,
+    pub value: String,
+}
+
+impl Color {
+    /// Groups colors by their `category`, see `group_by_category`.
+    pub fn group(options: Vec<ColorOption>) -> Vec<Color> {
+        group_by_category(options, |option| option.category.clone())
+            .into_iter()
+            .map(|(category, options)| {
+                Color {
+                    category: category,
+                    options: options,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The `category` filter for `Edmunds::colors_by_style_id`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorCategory {
+    Interior,
+    Exterior,
+}
+
+impl ColorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ColorCategory::Interior => "Interior",
+            ColorCategory::Exterior => "Exterior",
+        }
+    }
 }
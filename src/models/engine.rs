@@ -3,6 +3,7 @@ pub struct Engine {
     pub id: String,
     pub name: String,
     pub equipmentType: String,
+    pub availability: Option<String>,
     pub compressionRatio: Option<f32>,
     pub cylinder: Option<u8>,
     pub size: Option<f32>,
@@ -25,4 +25,26 @@ impl Equipment {
                         .find(|a| a.name == name)
                         .map(|a| a.value.parse::<T>().expect("parse error"))
     }
-}
\ No newline at end of file
+}
+
+/// Values of `equipmentType` the equipment endpoints can filter on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EquipmentType {
+    Other,
+    AudioSystem,
+    Mirrors,
+    Engine,
+    Transmission,
+}
+
+impl EquipmentType {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            EquipmentType::Other => "OTHER",
+            EquipmentType::AudioSystem => "AUDIO_SYSTEM",
+            EquipmentType::Mirrors => "MIRRORS",
+            EquipmentType::Engine => "ENGINE",
+            EquipmentType::Transmission => "TRANSMISSION",
+        }
+    }
+}
@@ -5,12 +5,14 @@ pub use self::car_option::*;
 pub use self::color::*;
 pub use self::transmission::*;
 pub use self::equipment::*;
+pub use self::vin_decode::*;
 
 mod engine;
 mod car_option;
 mod color;
 mod transmission;
 mod equipment;
+mod vin_decode;
 
 use std::sync::Arc;
 
@@ -20,6 +22,7 @@ pub struct Make {
     pub name: String,
     #[serde(rename="niceName")]
     pub nice_name: String,
+    #[serde(default)]
     pub models: Vec<Arc<Model>>,
 }
 
@@ -29,6 +32,7 @@ pub struct Model {
     pub name: String,
     #[serde(rename="niceName")]
     pub nice_name: String,
+    #[serde(default)]
     pub years: Vec<Arc<Year>>,
 }
 
@@ -71,6 +75,15 @@ impl Style {
     }
 }
 
+/// The styles of a model year with the count the API reports for them.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Styles {
+    pub styles: Vec<Style>,
+    /// 0 when the payload leaves `stylesCount` out.
+    #[serde(rename="stylesCount", default)]
+    pub count: u32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MPG {
     pub highway: u32,
@@ -112,23 +125,81 @@ pub struct Price {
     pub tmvRecommendedRating: Option<u32>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum State {
     New,
     Used,
     Future,
 }
 
-#[derive(Debug)]
+impl State {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            State::New => "new",
+            State::Used => "used",
+            State::Future => "future",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum View {
     Basic,
     Full,
 }
 
+impl View {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            View::Basic => "basic",
+            View::Full => "full",
+        }
+    }
+}
+
+/// Whether an engine, transmission or piece of equipment comes standard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Availability {
+    Standard,
+    Optional,
+    Used,
+}
+
+impl Availability {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Availability::Standard => "standard",
+            Availability::Optional => "optional",
+            Availability::Used => "used",
+        }
+    }
+}
+
+/// Groups `items` by category, keeping the order categories first appear in.
+/// Items without a category go under `Other`.
+fn group_by_category<T, F>(items: Vec<T>, category: F) -> Vec<(String, Vec<T>)>
+    where F: Fn(&T) -> Option<String>
+{
+    let mut grouped: Vec<(String, Vec<T>)> = vec![];
+
+    for item in items {
+        let category = category(&item).unwrap_or_else(|| "Other".to_string());
+        let position = grouped.iter().position(|&(ref c, _)| *c == category);
+
+        match position {
+            Some(i) => grouped[i].1.push(item),
+            None => grouped.push((category, vec![item])),
+        }
+    }
+
+    grouped
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(unused_imports)]
     use super::*;
+    use super::group_by_category;
     use serde_json;
 
     #[test]
@@ -168,4 +239,24 @@ mod tests {
         assert_eq!(expected.name, make.name);
         assert_eq!(3, make.years.len());
     }
+
+    #[test]
+    fn styles_without_count() {
+        let styles: Styles = serde_json::from_str(r#"{"styles":[]}"#).unwrap();
+
+        assert_eq!(0, styles.count);
+    }
+
+    #[test]
+    fn groups_in_first_seen_order() {
+        let items = vec![("Roof", 1), ("Interior", 2), ("Roof", 3), ("", 4)];
+
+        let grouped = group_by_category(items, |&(category, _)| {
+            if category.is_empty() { None } else { Some(category.to_string()) }
+        });
+
+        let categories: Vec<&str> = grouped.iter().map(|&(ref c, _)| &c[..]).collect();
+        assert_eq!(vec!["Roof", "Interior", "Other"], categories);
+        assert_eq!(vec![("Roof", 1), ("Roof", 3)], grouped[0].1);
+    }
 }
@@ -3,6 +3,7 @@ pub struct Transmission {
     pub id: String,
     pub name: String,
     pub equipmentType: String,
+    pub availability: Option<String>,
     pub automaticType: Option<String>,
     pub transmissionType: String,
     pub numberOfSpeeds: String,
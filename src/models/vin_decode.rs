@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use super::{Make, Model, Year, Style, Engine, Transmission, CarOption, Color, Price, Categories,
+            MPG};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VinDecode {
+    pub vin: Option<String>,
+    pub squishVin: Option<String>,
+    pub matchingType: Option<String>,
+    pub make: Make,
+    pub model: Model,
+    #[serde(default)]
+    pub years: Vec<Year>,
+
+    pub drivenWheels: Option<String>,
+    pub numOfDoors: Option<String>,
+    pub engine: Option<Engine>,
+    pub transmission: Option<Transmission>,
+    #[serde(default)]
+    pub options: Vec<CarOption>,
+    #[serde(default)]
+    pub colors: Vec<Color>,
+    pub manufacturerCode: Option<String>,
+    pub price: Option<Price>,
+    pub categories: Option<Categories>,
+    pub MPG: Option<MPG>,
+}
+
+impl VinDecode {
+    /// Every style the VIN matched, across all years.
+    pub fn styles(&self) -> Vec<Arc<Style>> {
+        self.years.iter().flat_map(|y| y.styles.iter().cloned()).collect()
+    }
+}
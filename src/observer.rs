@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+/// One request sent to Edmunds, reported to the client's `RequestObserver`.
+///
+/// Retried requests report one event per attempt; requests refused by the
+/// rate limiter are never sent and so are not reported.
+#[derive(Debug, Clone)]
+pub struct RequestEvent {
+    pub method: Method,
+    pub endpoint: String,
+    /// The query string with the `api_key` value redacted.
+    pub query: String,
+    /// `None` if no response was received.
+    pub status: Option<StatusCode>,
+    /// Time until the response headers arrived.
+    pub latency: Duration,
+    /// From `Content-Length`, if the response sent one.
+    pub response_size: Option<u64>,
+}
+
+/// Receives an event for every request the client sends.
+pub trait RequestObserver: Send + Sync {
+    fn on_request(&self, event: &RequestEvent);
+}
+
+/// The default observer: logs each request at debug level under the
+/// `edmunds` target, which prints nothing unless a logger is installed.
+pub struct LogObserver;
+
+impl RequestObserver for LogObserver {
+    fn on_request(&self, event: &RequestEvent) {
+        debug!(target: "edmunds",
+               "{} {}?{} status={:?} latency={}ms size={:?}",
+               event.method,
+               event.endpoint,
+               event.query,
+               event.status.map(|s| s.to_u16()),
+               event.latency.as_secs() * 1000 + event.latency.subsec_nanos() as u64 / 1_000_000,
+               event.response_size);
+    }
+}
+
+/// Replaces the value of every `api_key` parameter in `query` with `REDACTED`.
+pub fn redact(query: &str) -> String {
+    query.split('&')
+         .map(|pair| {
+             if pair.starts_with("api_key=") {
+                 "api_key=REDACTED"
+             } else {
+                 pair
+             }
+         })
+         .collect::<Vec<_>>()
+         .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_api_key() {
+        assert_eq!("fmt=json&api_key=REDACTED&state=new",
+                   redact("fmt=json&api_key=s3cr3t&state=new"));
+    }
+}
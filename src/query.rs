@@ -0,0 +1,211 @@
+use std::fmt;
+
+use url::form_urlencoded;
+use url::percent_encoding::{utf8_percent_encode, DEFAULT_ENCODE_SET};
+
+use models::{Availability, EquipmentType, State, View};
+use media::{PhotoCategory, ShotType};
+
+/// Query parameters for the Edmunds vehicle API.
+///
+/// Unset parameters are left out so the API applies its own defaults.
+///
+/// ```
+/// use edmunds::{Query, State, View};
+///
+/// let query = Query::new().state(State::Used).year(2014).view(View::Full);
+/// assert_eq!("fmt=json&state=used&year=2014&view=full", query.to_string());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    state: Option<State>,
+    year: Option<u16>,
+    view: Option<View>,
+    submodel: Option<String>,
+    category: Option<String>,
+    availability: Option<Availability>,
+    equipment_type: Option<EquipmentType>,
+    shot_type: Option<ShotType>,
+    width: Option<u32>,
+    page_num: Option<u32>,
+    page_size: Option<u32>,
+    extra: Vec<(String, String)>,
+}
+
+impl Query {
+    pub fn new() -> Self {
+        Query::default()
+    }
+
+    pub fn state(mut self, state: State) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    pub fn year(mut self, year: u16) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    pub fn view(mut self, view: View) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    pub fn submodel<S: Into<String>>(mut self, submodel: S) -> Self {
+        self.submodel = Some(submodel.into());
+        self
+    }
+
+    pub fn category<S: Into<String>>(mut self, category: S) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn availability(mut self, availability: Availability) -> Self {
+        self.availability = Some(availability);
+        self
+    }
+
+    pub fn equipment_type(mut self, equipment_type: EquipmentType) -> Self {
+        self.equipment_type = Some(equipment_type);
+        self
+    }
+
+    /// The `category` filter for photos.
+    pub fn photo_category(self, category: PhotoCategory) -> Self {
+        self.category(category.as_str())
+    }
+
+    pub fn shot_type(mut self, shot_type: ShotType) -> Self {
+        self.shot_type = Some(shot_type);
+        self
+    }
+
+    /// Photo width in pixels.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn page_num(mut self, page_num: u32) -> Self {
+        self.page_num = Some(page_num);
+        self
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Adds a parameter this type doesn't model. Repeated keys are all sent.
+    pub fn param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.extra.push((key.into(), value.into()));
+        self
+    }
+
+    fn pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = vec![];
+
+        // The client only parses JSON.
+        pairs.push(("fmt".to_string(), "json".to_string()));
+
+        if let Some(state) = self.state {
+            pairs.push(("state".to_string(), state.as_str().to_string()));
+        }
+        if let Some(year) = self.year {
+            pairs.push(("year".to_string(), year.to_string()));
+        }
+        if let Some(view) = self.view {
+            pairs.push(("view".to_string(), view.as_str().to_string()));
+        }
+        if let Some(ref submodel) = self.submodel {
+            pairs.push(("submodel".to_string(), submodel.clone()));
+        }
+        if let Some(ref category) = self.category {
+            pairs.push(("category".to_string(), category.clone()));
+        }
+        if let Some(availability) = self.availability {
+            pairs.push(("availability".to_string(), availability.as_str().to_string()));
+        }
+        if let Some(equipment_type) = self.equipment_type {
+            pairs.push(("equipmentType".to_string(), equipment_type.as_str().to_string()));
+        }
+        if let Some(shot_type) = self.shot_type {
+            pairs.push(("shottype".to_string(), shot_type.as_str().to_string()));
+        }
+        if let Some(width) = self.width {
+            pairs.push(("width".to_string(), width.to_string()));
+        }
+        if let Some(page_num) = self.page_num {
+            pairs.push(("pagenum".to_string(), page_num.to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            pairs.push(("pagesize".to_string(), page_size.to_string()));
+        }
+
+        pairs.extend(self.extra.iter().cloned());
+        pairs
+    }
+}
+
+/// Formats as a percent-encoded query string without the leading `?`.
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&form_urlencoded::serialize(&self.pairs()))
+    }
+}
+
+/// Percent-encodes a make, model or other value interpolated into a path.
+pub fn path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, DEFAULT_ENCODE_SET).replace("/", "%2F")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::{Availability, EquipmentType, State};
+    use media::{PhotoCategory, ShotType};
+
+    #[test]
+    fn defaults_to_json_only() {
+        assert_eq!("fmt=json", Query::new().to_string());
+    }
+
+    #[test]
+    fn encodes_values() {
+        let query = Query::new()
+                        .state(State::New)
+                        .submodel("sedan & coupe")
+                        .param("category", "4dr SUV")
+                        .page_num(2);
+
+        assert_eq!("fmt=json&state=new&submodel=sedan+%26+coupe&pagenum=2&category=4dr+SUV",
+                   query.to_string());
+    }
+
+    #[test]
+    fn encodes_equipment_filters() {
+        let query = Query::new()
+                        .equipment_type(EquipmentType::AudioSystem)
+                        .availability(Availability::Standard);
+
+        assert_eq!("fmt=json&availability=standard&equipmentType=AUDIO_SYSTEM",
+                   query.to_string());
+    }
+
+    #[test]
+    fn encodes_photo_filters() {
+        let query = Query::new()
+                        .photo_category(PhotoCategory::Exterior)
+                        .shot_type(ShotType::FrontQuarter)
+                        .width(500);
+
+        assert_eq!("fmt=json&category=EXTERIOR&shottype=FQ&width=500", query.to_string());
+    }
+
+    #[test]
+    fn encodes_path_segments() {
+        assert_eq!("land%20rover%2Fx", path_segment("land rover/x"));
+    }
+}
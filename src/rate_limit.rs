@@ -0,0 +1,265 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use error::{Error, Result};
+
+/// What to do when a request would go over budget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitMode {
+    /// Sleep until the per-second bucket refills. An exhausted daily budget
+    /// still fails immediately rather than blocking for hours.
+    Block,
+    /// Return `Error::RateLimited` as soon as either budget is exhausted.
+    FailFast,
+}
+
+/// The budget a request was refused by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    PerSecond,
+    PerDay,
+}
+
+/// Remaining budget as seen by the client; `None` means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitStatus {
+    pub remaining_this_second: Option<u32>,
+    pub remaining_today: Option<u32>,
+    pub used_today: u32,
+}
+
+/// A token bucket for queries per second plus a counter for the daily quota.
+///
+/// The day is a rolling 24 hour window starting with the first request, which
+/// is close enough to how Mashery resets keys without knowing its clock.
+pub struct RateLimiter {
+    per_second: Option<u32>,
+    per_day: Option<u32>,
+    mode: LimitMode,
+    state: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set by the first request counted in the current 24 hour window.
+    day_started: Option<Instant>,
+    used_today: u32,
+}
+
+impl RateLimiter {
+    /// A limiter that never refuses a request.
+    pub fn unlimited() -> Self {
+        RateLimiter::new(LimitMode::FailFast)
+    }
+
+    pub fn new(mode: LimitMode) -> Self {
+        let now = Instant::now();
+
+        RateLimiter {
+            per_second: None,
+            per_day: None,
+            mode: mode,
+            state: Mutex::new(Bucket {
+                tokens: 0.0,
+                refilled_at: now,
+                day_started: None,
+                used_today: 0,
+            }),
+        }
+    }
+
+    /// Requests allowed per second; `0` means no per second limit.
+    pub fn per_second(mut self, limit: u32) -> Self {
+        if limit == 0 {
+            self.per_second = None;
+            return self;
+        }
+
+        self.per_second = Some(limit);
+        self.state.get_mut().unwrap().tokens = limit as f64;
+        self
+    }
+
+    /// Requests allowed per 24 hours; `0` means no daily limit.
+    pub fn per_day(mut self, limit: u32) -> Self {
+        self.per_day = if limit == 0 { None } else { Some(limit) };
+        self
+    }
+
+    pub fn mode(&self) -> LimitMode {
+        self.mode
+    }
+
+    /// Takes one request's worth of budget, blocking or failing per `LimitMode`.
+    pub fn acquire(&self) -> Result<()> {
+        loop {
+            let wait = {
+                let now = Instant::now();
+                let mut bucket = self.state.lock().unwrap();
+                bucket.refresh(self.per_second, now);
+
+                if let Some(per_day) = self.per_day {
+                    if bucket.used_today >= per_day {
+                        return Err(Error::RateLimited(Budget::PerDay));
+                    }
+                }
+
+                match self.per_second {
+                    Some(per_second) if bucket.tokens < 1.0 => {
+                        if self.mode == LimitMode::FailFast {
+                            return Err(Error::RateLimited(Budget::PerSecond));
+                        }
+
+                        from_secs_f64((1.0 - bucket.tokens) / per_second as f64)
+                    }
+                    Some(_) => {
+                        bucket.tokens -= 1.0;
+                        bucket.count(now);
+                        return Ok(());
+                    }
+                    None => {
+                        bucket.count(now);
+                        return Ok(());
+                    }
+                }
+            };
+
+            thread::sleep(wait);
+        }
+    }
+
+    pub fn status(&self) -> RateLimitStatus {
+        let mut bucket = self.state.lock().unwrap();
+        bucket.refresh(self.per_second, Instant::now());
+
+        RateLimitStatus {
+            remaining_this_second: self.per_second.map(|_| bucket.tokens as u32),
+            remaining_today: self.per_day.map(|limit| limit.saturating_sub(bucket.used_today)),
+            used_today: bucket.used_today,
+        }
+    }
+}
+
+impl Bucket {
+    fn refresh(&mut self, per_second: Option<u32>, now: Instant) {
+        if let Some(per_second) = per_second {
+            let elapsed = secs_f64(now.duration_since(self.refilled_at));
+            let capacity = per_second as f64;
+
+            self.tokens = (self.tokens + elapsed * capacity).min(capacity);
+            self.refilled_at = now;
+        }
+
+        if let Some(day_started) = self.day_started {
+            if now.duration_since(day_started) >= Duration::from_secs(24 * 60 * 60) {
+                self.day_started = None;
+                self.used_today = 0;
+            }
+        }
+    }
+
+    fn count(&mut self, now: Instant) {
+        if self.day_started.is_none() {
+            self.day_started = Some(now);
+        }
+        self.used_today += 1;
+    }
+}
+
+fn secs_f64(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1_000_000_000.0
+}
+
+fn from_secs_f64(secs: f64) -> Duration {
+    let whole = secs.trunc();
+    Duration::new(whole as u64, ((secs - whole) * 1_000_000_000.0) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use error::Error;
+
+    #[test]
+    fn fail_fast_per_second() {
+        let limiter = RateLimiter::new(LimitMode::FailFast).per_second(2);
+
+        assert!(limiter.acquire().is_ok());
+        assert!(limiter.acquire().is_ok());
+
+        match limiter.acquire() {
+            Err(Error::RateLimited(Budget::PerSecond)) => {}
+            other => panic!("expected per second limit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn per_day_is_never_waited_on() {
+        let limiter = RateLimiter::new(LimitMode::Block).per_day(1);
+
+        assert!(limiter.acquire().is_ok());
+
+        match limiter.acquire() {
+            Err(Error::RateLimited(Budget::PerDay)) => {}
+            other => panic!("expected per day limit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn zero_per_second_is_unlimited() {
+        let limiter = RateLimiter::new(LimitMode::Block).per_second(0);
+
+        for _ in 0..3 {
+            assert!(limiter.acquire().is_ok());
+        }
+        assert_eq!(None, limiter.status().remaining_this_second);
+    }
+
+    #[test]
+    fn zero_per_day_is_unlimited() {
+        let limiter = RateLimiter::new(LimitMode::FailFast).per_day(0);
+
+        for _ in 0..3 {
+            assert!(limiter.acquire().is_ok());
+        }
+        assert_eq!(None, limiter.status().remaining_today);
+        assert_eq!(3, limiter.status().used_today);
+    }
+
+    #[test]
+    fn block_waits_for_refill() {
+        let limiter = RateLimiter::new(LimitMode::Block).per_second(10);
+
+        for _ in 0..11 {
+            limiter.acquire().unwrap();
+        }
+
+        assert_eq!(11, limiter.status().used_today);
+    }
+
+    #[test]
+    fn status_counts_down() {
+        let limiter = RateLimiter::new(LimitMode::FailFast).per_second(5).per_day(100);
+
+        limiter.acquire().unwrap();
+        limiter.acquire().unwrap();
+
+        let status = limiter.status();
+        assert_eq!(Some(98), status.remaining_today);
+        assert_eq!(2, status.used_today);
+    }
+
+    #[test]
+    fn unlimited_still_counts() {
+        let limiter = RateLimiter::unlimited();
+
+        limiter.acquire().unwrap();
+
+        let status = limiter.status();
+        assert_eq!(None, status.remaining_today);
+        assert_eq!(None, status.remaining_this_second);
+        assert_eq!(1, status.used_today);
+    }
+}
@@ -0,0 +1,38 @@
+/// A safety recall campaign filed for a model year.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Recall {
+    pub id: u32,
+    /// NHTSA campaign number, e.g. `14V123000`.
+    #[serde(rename="recallNumber")]
+    pub campaign_number: String,
+    #[serde(rename="manufacturerRecallNumber")]
+    pub manufacturer_recall_number: Option<String>,
+    #[serde(rename="componentDescription")]
+    pub component: String,
+    #[serde(rename="defectDescription")]
+    pub summary: String,
+    #[serde(rename="defectConsequence")]
+    pub consequence: String,
+    #[serde(rename="defectCorrectiveAction")]
+    pub remedy: String,
+    /// As reported, `MM/DD/YYYY`.
+    #[serde(rename="recallDate")]
+    pub date: String,
+    #[serde(rename="numberOfVehiclesAffected")]
+    pub vehicles_affected: Option<u32>,
+}
+
+/// A technical service bulletin issued to dealers for a model year.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServiceBulletin {
+    pub id: u32,
+    #[serde(rename="bulletinNumber")]
+    pub bulletin_number: String,
+    #[serde(rename="componentDescription")]
+    pub component: String,
+    #[serde(rename="summaryText")]
+    pub summary: String,
+    /// As reported, `MM/DD/YYYY`.
+    #[serde(rename="bulletinDate")]
+    pub date: String,
+}
@@ -0,0 +1,208 @@
+use std::cmp;
+use std::fmt;
+use std::time::Duration;
+
+use rand;
+
+use error::Error;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Broad kinds of failure a `RetryPolicy` may choose to retry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorClass {
+    /// The request never got a response: connection refused, reset, timed out.
+    Transport,
+    /// A 5xx from Edmunds or Mashery.
+    Server,
+    /// A 403 for going over the key's queries per second limit.
+    OverQps,
+}
+
+impl Error {
+    /// The retryable class of this error, or `None` if retrying cannot help.
+    pub fn class(&self) -> Option<ErrorClass> {
+        match *self {
+            Error::Http(_) | Error::Io(_) => Some(ErrorClass::Transport),
+            Error::Server(..) => Some(ErrorClass::Server),
+            Error::OverQps(_) => Some(ErrorClass::OverQps),
+            _ => None,
+        }
+    }
+}
+
+/// Passed to the `on_retry` hook before sleeping for the next attempt.
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    pub endpoint: &'a str,
+    /// The attempt that just failed, starting at 1.
+    pub attempt: u32,
+    pub error: &'a Error,
+    pub delay: Duration,
+}
+
+/// Exponential backoff for the GETs sent by `Edmunds::call_send`.
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)` capped at
+/// `max_delay`; with jitter on, a uniformly random fraction of that is used
+/// instead so that parallel workers don't retry in lockstep.
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_on: Vec<ErrorClass>,
+    on_retry: Option<Box<Fn(&RetryEvent) + Send + Sync>>,
+}
+
+impl RetryPolicy {
+    /// Three attempts, 500ms base delay, 10s max delay, jittered, retrying
+    /// every `ErrorClass`.
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            retry_on: vec![ErrorClass::Transport, ErrorClass::Server, ErrorClass::OverQps],
+            on_retry: None,
+        }
+    }
+
+    /// A policy that sends every request exactly once.
+    pub fn never() -> Self {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /// Total attempts including the first; values below 1 are treated as 1.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = cmp::max(max_attempts, 1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retry_on(mut self, classes: &[ErrorClass]) -> Self {
+        self.retry_on = classes.to_vec();
+        self
+    }
+
+    /// Called once for every retry, e.g. to log it.
+    pub fn on_retry<F>(mut self, hook: F) -> Self
+        where F: Fn(&RetryEvent) + Send + Sync + 'static
+    {
+        self.on_retry = Some(Box::new(hook));
+        self
+    }
+
+    /// Whether a request that failed with `error` on `attempt` should be sent again.
+    pub fn should_retry(&self, error: &Error, attempt: u32) -> bool {
+        attempt < self.max_attempts &&
+        error.class().map_or(false, |class| self.retry_on.contains(&class))
+    }
+
+    /// The delay before the attempt following `attempt`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exponent = cmp::min(attempt.saturating_sub(1), 31);
+        let backoff = cmp::min(nanos(self.base_delay).saturating_mul(1u64 << exponent),
+                               nanos(self.max_delay));
+
+        let delay = if self.jitter {
+            (backoff as f64 * rand::random::<f64>()) as u64
+        } else {
+            backoff
+        };
+
+        Duration::new(delay / NANOS_PER_SEC, (delay % NANOS_PER_SEC) as u32)
+    }
+
+    pub fn notify(&self, event: &RetryEvent) {
+        if let Some(ref hook) = self.on_retry {
+            hook(event);
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_secs()
+            .saturating_mul(NANOS_PER_SEC)
+            .saturating_add(duration.subsec_nanos() as u64)
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+         .field("max_attempts", &self.max_attempts)
+         .field("base_delay", &self.base_delay)
+         .field("max_delay", &self.max_delay)
+         .field("jitter", &self.jitter)
+         .field("retry_on", &self.retry_on)
+         .field("on_retry", &self.on_retry.is_some())
+         .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use error::{self, Error};
+    use rate_limit::Budget;
+    use hyper::status::StatusCode;
+
+    #[test]
+    fn delay_doubles_up_to_max() {
+        let policy = RetryPolicy::new()
+                         .jitter(false)
+                         .base_delay(Duration::from_millis(100))
+                         .max_delay(Duration::from_millis(350));
+
+        assert_eq!(Duration::from_millis(100), policy.delay(1));
+        assert_eq!(Duration::from_millis(200), policy.delay(2));
+        assert_eq!(Duration::from_millis(350), policy.delay(3));
+        assert_eq!(Duration::from_millis(350), policy.delay(40));
+    }
+
+    #[test]
+    fn jitter_stays_below_backoff() {
+        let policy = RetryPolicy::new().base_delay(Duration::from_millis(100));
+
+        for _ in 0..20 {
+            assert!(policy.delay(1) <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn only_listed_classes_are_retried() {
+        let policy = RetryPolicy::new().retry_on(&[ErrorClass::Server]);
+
+        let server = error::from_response(StatusCode::BadGateway, None, "");
+        let over_qps = error::from_response(StatusCode::Forbidden,
+                                            Some("ERR_403_DEVELOPER_OVER_QPS"),
+                                            "");
+
+        assert!(policy.should_retry(&server, 1));
+        assert!(!policy.should_retry(&server, 3));
+        assert!(!policy.should_retry(&over_qps, 1));
+        assert!(!policy.should_retry(&Error::RateLimited(Budget::PerSecond), 1));
+    }
+}
@@ -0,0 +1,24 @@
+/// One ownership cost, year by year over the first five years.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CostSchedule {
+    /// Cost in each year of ownership, first year first.
+    pub values: Vec<f64>,
+    /// Sum over all five years.
+    pub total: f64,
+}
+
+/// Edmunds True Cost to Own: what a style costs to keep for five years in a zip code.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrueCostToOwn {
+    pub depreciation: CostSchedule,
+    pub financing: CostSchedule,
+    pub insurance: CostSchedule,
+    #[serde(rename="taxesAndFees")]
+    pub taxes_and_fees: CostSchedule,
+    pub fuel: CostSchedule,
+    pub maintenance: CostSchedule,
+    pub repairs: CostSchedule,
+    /// All of the above combined; `total.total` is the five-year cost to own.
+    #[serde(rename="totalCost")]
+    pub total: CostSchedule,
+}
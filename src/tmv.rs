@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+/// One line of a TMV breakdown, priced three ways.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TmvPrice {
+    #[serde(rename="baseMSRP")]
+    pub msrp: f64,
+    #[serde(rename="baseInvoice")]
+    pub invoice: f64,
+    pub tmv: f64,
+}
+
+/// True Market Value of a new vehicle configured with options and a color.
+///
+/// The total is the national base price plus the regional adjustment, every
+/// option, the color and the destination charge.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewTmv {
+    #[serde(rename="nationalBasePrice")]
+    pub base: TmvPrice,
+    #[serde(rename="regionalAdjustment")]
+    pub regional_adjustment: TmvPrice,
+    /// Keyed by option id.
+    #[serde(rename="optionTMVPrices", default)]
+    pub options: BTreeMap<String, TmvPrice>,
+    #[serde(rename="colorTMVPrice")]
+    pub color: Option<TmvPrice>,
+    #[serde(rename="destinationCharge", default)]
+    pub destination: f64,
+    #[serde(rename="totalWithOptions")]
+    pub total: TmvPrice,
+    #[serde(rename="tmvCurrency")]
+    pub currency: Option<String>,
+}
+
+/// Condition of a used vehicle, as graded by Edmunds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Condition {
+    Outstanding,
+    Clean,
+    Average,
+    Rough,
+    Damaged,
+}
+
+impl Condition {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Condition::Outstanding => "Outstanding",
+            Condition::Clean => "Clean",
+            Condition::Average => "Average",
+            Condition::Rough => "Rough",
+            Condition::Damaged => "Damaged",
+        }
+    }
+}
+
+/// One line of a used TMV appraisal, priced for each way of selling the vehicle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UsedTmvPrice {
+    #[serde(rename="usedTradeIn")]
+    pub trade_in: f64,
+    #[serde(rename="usedPrivateParty")]
+    pub private_party: f64,
+    #[serde(rename="usedTmvRetail")]
+    pub dealer_retail: f64,
+}
+
+/// True Market Value of a used vehicle in a given condition and mileage.
+///
+/// The total is the national base price plus the regional, option, color,
+/// mileage and condition adjustments.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsedTmv {
+    #[serde(rename="nationalBasePrice")]
+    pub base: UsedTmvPrice,
+    #[serde(rename="regionalAdjustment")]
+    pub regional_adjustment: UsedTmvPrice,
+    /// Keyed by option id.
+    #[serde(rename="optionTMVPrices", default)]
+    pub options: BTreeMap<String, UsedTmvPrice>,
+    #[serde(rename="colorTMVPrice")]
+    pub color: Option<UsedTmvPrice>,
+    #[serde(rename="mileageAdjustment")]
+    pub mileage_adjustment: UsedTmvPrice,
+    #[serde(rename="conditionAdjustment")]
+    pub condition_adjustment: UsedTmvPrice,
+    #[serde(rename="totalWithOptions")]
+    pub total: UsedTmvPrice,
+    #[serde(rename="tmvCurrency")]
+    pub currency: Option<String>,
+}
@@ -0,0 +1,326 @@
+use std::ascii::AsciiExt;
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Weights applied to each position when computing the check digit.
+const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// World Manufacturer Identifiers, named as in `Make::name`.
+const MANUFACTURERS: &'static [(&'static str, &'static str)] = &[
+    ("19U", "Acura"), ("19V", "Acura"), ("JH4", "Acura"), ("2HN", "Acura"), ("5J8", "Acura"),
+    ("ZAR", "Alfa Romeo"),
+    ("SCF", "Aston Martin"),
+    ("WAU", "Audi"), ("WA1", "Audi"), ("TRU", "Audi"),
+    ("WBA", "BMW"), ("WBS", "BMW"), ("WBY", "BMW"), ("5UX", "BMW"), ("4US", "BMW"),
+    ("SCB", "Bentley"),
+    ("1G4", "Buick"), ("2G4", "Buick"),
+    ("1G6", "Cadillac"),
+    ("1G1", "Chevrolet"), ("2G1", "Chevrolet"), ("3G1", "Chevrolet"), ("1GC", "Chevrolet"),
+    ("1GN", "Chevrolet"),
+    ("1C3", "Chrysler"), ("2C3", "Chrysler"),
+    ("1B3", "Dodge"), ("2B3", "Dodge"),
+    ("ZFA", "FIAT"),
+    ("ZFF", "Ferrari"),
+    ("1FA", "Ford"), ("1FM", "Ford"), ("1FT", "Ford"), ("2FM", "Ford"), ("3FA", "Ford"),
+    ("1GK", "GMC"), ("1GT", "GMC"), ("3GT", "GMC"),
+    ("1HG", "Honda"), ("2HG", "Honda"), ("JHM", "Honda"), ("5FN", "Honda"), ("5J6", "Honda"),
+    ("KMH", "Hyundai"), ("KM8", "Hyundai"), ("5NP", "Hyundai"),
+    ("JNK", "Infiniti"), ("JNR", "Infiniti"), ("5N3", "Infiniti"),
+    ("SAJ", "Jaguar"),
+    ("1J4", "Jeep"), ("1J8", "Jeep"),
+    ("KNA", "Kia"), ("KND", "Kia"), ("5XX", "Kia"), ("5XY", "Kia"),
+    ("ZHW", "Lamborghini"),
+    ("SAL", "Land Rover"),
+    ("JTH", "Lexus"), ("JTJ", "Lexus"), ("2T2", "Lexus"),
+    ("1LN", "Lincoln"), ("5LM", "Lincoln"),
+    ("SCC", "Lotus"),
+    ("WMW", "MINI"),
+    ("ZAM", "Maserati"),
+    ("JM1", "Mazda"), ("JM3", "Mazda"), ("3MZ", "Mazda"),
+    ("SBM", "McLaren"),
+    ("WDB", "Mercedes-Benz"), ("WDC", "Mercedes-Benz"), ("WDD", "Mercedes-Benz"),
+    ("4JG", "Mercedes-Benz"), ("55S", "Mercedes-Benz"),
+    ("JA3", "Mitsubishi"), ("JA4", "Mitsubishi"), ("4A3", "Mitsubishi"),
+    ("JN1", "Nissan"), ("JN8", "Nissan"), ("1N4", "Nissan"), ("1N6", "Nissan"), ("3N1", "Nissan"),
+    ("5N1", "Nissan"),
+    ("WP0", "Porsche"), ("WP1", "Porsche"),
+    ("1C6", "Ram"), ("3C6", "Ram"),
+    ("SCA", "Rolls-Royce"),
+    ("JTK", "Scion"), ("JTL", "Scion"),
+    ("JF1", "Subaru"), ("JF2", "Subaru"), ("4S3", "Subaru"), ("4S4", "Subaru"),
+    ("5YJ", "Tesla"),
+    ("JTD", "Toyota"), ("JTE", "Toyota"), ("JTN", "Toyota"), ("JT2", "Toyota"), ("2T1", "Toyota"),
+    ("4T1", "Toyota"), ("4T3", "Toyota"), ("5TD", "Toyota"), ("5TF", "Toyota"),
+    ("WVW", "Volkswagen"), ("WVG", "Volkswagen"), ("1VW", "Volkswagen"), ("3VW", "Volkswagen"),
+    ("YV1", "Volvo"), ("YV4", "Volvo"),
+    ("WME", "smart"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VinError {
+    /// A VIN is exactly 17 characters; this is how many were given.
+    Length(usize),
+    /// Only digits and letters other than I, O and Q are allowed.
+    InvalidCharacter(usize, char),
+    /// The 9th character does not match the one computed from the rest.
+    CheckDigit { expected: char, found: char },
+}
+
+impl fmt::Display for VinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VinError::Length(len) => write!(f, "a vin has 17 characters, not {}", len),
+            VinError::InvalidCharacter(position, c) => {
+                write!(f, "invalid character {:?} at position {}", c, position + 1)
+            }
+            VinError::CheckDigit { expected, found } => {
+                write!(f, "check digit is {:?}, expected {:?}", found, expected)
+            }
+        }
+    }
+}
+
+impl StdError for VinError {
+    fn description(&self) -> &str {
+        match *self {
+            VinError::Length(_) => "invalid vin length",
+            VinError::InvalidCharacter(..) => "invalid vin character",
+            VinError::CheckDigit { .. } => "invalid vin check digit",
+        }
+    }
+}
+
+/// A syntactically valid 17 character VIN, upper-cased.
+///
+/// ```
+/// use edmunds::Vin;
+///
+/// let vin: Vin = "WBY1Z2C51EV123456".parse().unwrap();
+///
+/// assert_eq!(Some("BMW"), vin.manufacturer());
+/// assert_eq!(Some(2014), vin.model_year());
+/// assert_eq!("WBY1Z2C5EV", vin.squish());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Vin(String);
+
+impl Vin {
+    /// Validates length, characters and the North American check digit.
+    pub fn parse(vin: &str) -> Result<Vin, VinError> {
+        let vin = try!(Vin::parse_unchecked(vin));
+
+        let expected = vin.expected_check_digit();
+        let found = vin.check_digit();
+
+        if expected != found {
+            return Err(VinError::CheckDigit {
+                expected: expected,
+                found: found,
+            });
+        }
+
+        Ok(vin)
+    }
+
+    /// Validates length and characters only, for VINs from markets that
+    /// don't use the check digit.
+    pub fn parse_unchecked(vin: &str) -> Result<Vin, VinError> {
+        let vin = vin.trim().to_ascii_uppercase();
+
+        let len = vin.chars().count();
+        if len != 17 {
+            return Err(VinError::Length(len));
+        }
+
+        for (position, c) in vin.chars().enumerate() {
+            if transliterate(c).is_none() {
+                return Err(VinError::InvalidCharacter(position, c));
+            }
+        }
+
+        Ok(Vin(vin))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// World Manufacturer Identifier, the first 3 characters.
+    pub fn wmi(&self) -> &str {
+        &self.0[0..3]
+    }
+
+    /// Vehicle Descriptor Section, characters 4 to 8.
+    pub fn vds(&self) -> &str {
+        &self.0[3..8]
+    }
+
+    pub fn check_digit(&self) -> char {
+        self.char_at(8)
+    }
+
+    /// The model year from the 10th character.
+    ///
+    /// The 30 year cycle of year codes is disambiguated the way North
+    /// American passenger vehicles are: a letter in the 7th position means
+    /// 2010 or later.
+    pub fn model_year(&self) -> Option<u16> {
+        let code = self.char_at(9);
+        let cycle = if self.char_at(6).is_digit(10) { 1980 } else { 2010 };
+
+        let offset = match "ABCDEFGHJKLMNPRSTVWXY123456789".find(code) {
+            Some(offset) => offset as u16,
+            None => return None,
+        };
+
+        Some(cycle + offset)
+    }
+
+    /// The assembly plant, the 11th character.
+    pub fn plant_code(&self) -> char {
+        self.char_at(10)
+    }
+
+    /// The production serial number, the last 6 characters.
+    pub fn serial(&self) -> &str {
+        &self.0[11..17]
+    }
+
+    /// The manufacturer name for the WMI, comparable to `Make::name`.
+    pub fn manufacturer(&self) -> Option<&'static str> {
+        MANUFACTURERS.iter()
+                     .find(|&&(wmi, _)| wmi == self.wmi())
+                     .map(|&(_, name)| name)
+    }
+
+    pub fn squish(&self) -> String {
+        self.0[0..8].to_string() + &self.0[9..11]
+    }
+
+    fn expected_check_digit(&self) -> char {
+        let sum = self.0
+                      .chars()
+                      .zip(WEIGHTS.iter())
+                      .map(|(c, weight)| transliterate(c).unwrap_or(0) * weight)
+                      .fold(0, |sum, value| sum + value);
+
+        match sum % 11 {
+            10 => 'X',
+            remainder => (b'0' + remainder as u8) as char,
+        }
+    }
+
+    fn char_at(&self, position: usize) -> char {
+        self.0.as_bytes()[position] as char
+    }
+}
+
+impl FromStr for Vin {
+    type Err = VinError;
+
+    fn from_str(vin: &str) -> Result<Vin, VinError> {
+        Vin::parse(vin)
+    }
+}
+
+impl fmt::Display for Vin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The numeric value of a VIN character for the check digit, `None` if the
+/// character isn't allowed in a VIN.
+fn transliterate(c: char) -> Option<u32> {
+    match c {
+        '0'...'9' => c.to_digit(10),
+        'A'...'H' => Some(c as u32 - 'A' as u32 + 1),
+        'J'...'N' => Some(c as u32 - 'J' as u32 + 1),
+        'P' => Some(7),
+        'R' => Some(9),
+        'S'...'Z' => Some(c as u32 - 'S' as u32 + 2),
+        _ => None,
+    }
+}
+
+/// Derives the squish VIN Edmunds indexes styles by from a full 17 character
+/// VIN: the first 8 characters plus the 10th and 11th, dropping the check
+/// digit and the serial number.
+///
+/// Returns `None` unless `Vin::parse_unchecked` accepts `vin`; the check digit
+/// is not verified.
+///
+/// ```
+/// use edmunds::squish_vin;
+///
+/// assert_eq!(Some("19VDE1F3EE".to_string()), squish_vin("19vde1f3xee008012"));
+/// ```
+pub fn squish_vin(vin: &str) -> Option<String> {
+    Vin::parse_unchecked(vin).ok().map(|vin| vin.squish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squishes_vin() {
+        assert_eq!(Some("WBY1Z2C5EV".to_string()), squish_vin("WBY1Z2C57EVX51234"));
+    }
+
+    #[test]
+    fn rejects_bad_length() {
+        assert_eq!(None, squish_vin("WBY1Z2C57EV"));
+        assert_eq!(None, squish_vin("WBY1Z2C57EVX51234X"));
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        assert_eq!(None, squish_vin("WBY1Z2C57EVX5123-"));
+        assert_eq!(None, squish_vin("WBY1Z2C57EVX512é"));
+        assert_eq!(None, squish_vin("WBY1Z2C57EVO51234"));
+    }
+
+    #[test]
+    fn parses_valid_vin() {
+        let vin = Vin::parse("19vde1f3xee008012").unwrap();
+
+        assert_eq!("19VDE1F3XEE008012", vin.as_str());
+        assert_eq!("19V", vin.wmi());
+        assert_eq!("DE1F3", vin.vds());
+        assert_eq!('X', vin.check_digit());
+        assert_eq!(Some(2014), vin.model_year());
+        assert_eq!('E', vin.plant_code());
+        assert_eq!("008012", vin.serial());
+        assert_eq!(Some("Acura"), vin.manufacturer());
+    }
+
+    #[test]
+    fn rejects_wrong_check_digit() {
+        assert_eq!(Err(VinError::CheckDigit { expected: '1', found: '7' }),
+                   Vin::parse("WBY1Z2C57EV123456"));
+        assert!(Vin::parse_unchecked("WBY1Z2C57EV123456").is_ok());
+    }
+
+    #[test]
+    fn rejects_i_o_q() {
+        assert_eq!(Err(VinError::InvalidCharacter(12, 'O')),
+                   Vin::parse("WBY1Z2C51EV1O3456"));
+        assert_eq!(Err(VinError::Length(3)), Vin::parse("WBY"));
+    }
+
+    #[test]
+    fn model_year_cycles() {
+        // numeric 7th character, 1980-2009 cycle
+        assert_eq!(Some(1994), Vin::parse_unchecked("1G1JC5240R7000000").unwrap().model_year());
+        assert_eq!(Some(2005), Vin::parse_unchecked("1HGCM56405A000000").unwrap().model_year());
+        // alphabetic 7th character, 2010-2039 cycle
+        assert_eq!(Some(2016), Vin::parse_unchecked("19UDE2F36GA000123").unwrap().model_year());
+    }
+
+    #[test]
+    fn unknown_manufacturer() {
+        assert_eq!(None, Vin::parse_unchecked("ZZZ1Z2C51EV123456").unwrap().manufacturer());
+    }
+}
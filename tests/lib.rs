@@ -1,6 +1,6 @@
 extern crate edmunds;
 
-use edmunds::{ Edmunds, State };
+use edmunds::{ Edmunds, Query, State };
 
 use std::io::Read;
 
@@ -11,7 +11,7 @@ const EDMUNDS_API_KEY: &'static str = include_str!("api_key");
 fn all_makes() {
     let api = Edmunds::new(EDMUNDS_API_KEY);
 
-    let makes = api.all_makes(State::New).unwrap();
+    let makes = api.all_makes(&Query::new().state(State::New)).unwrap();
 
     //println!("{:?}", makes);
 }
@@ -21,7 +21,7 @@ fn all_makes() {
 fn make_models() {
     let api = Edmunds::new(EDMUNDS_API_KEY);
 
-    let models = api.models_by_make("bmw").unwrap();
+    let models = api.models_by_make("bmw", &Query::new().state(State::New)).unwrap();
 
     //println!("{:?}", models);
 }
\ No newline at end of file